@@ -0,0 +1,297 @@
+use crate::Grid;
+use std::thread;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Neighbourhood {
+    // Up, down, left and right
+    VonNeumann,
+    // Diagonals as well
+    Moore,
+}
+
+impl Neighbourhood {
+    // (row, column) offsets in storage order
+    fn offsets(&self) -> &'static [(i32, i32)] {
+        match self {
+            Neighbourhood::VonNeumann => &[(-1, 0), (0, 1), (1, 0), (0, -1)],
+            Neighbourhood::Moore => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+            ],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // rule gets the cell and its in-bounds neighbours and returns the cell's next
+    // value. The next generation is built in a new buffer and swapped in, use
+    // Automaton to reuse the buffer over many steps
+    pub fn step<F>(&mut self, neighbourhood: Neighbourhood, rule: F)
+    where
+        F: Fn(&T, &[&T]) -> T,
+    {
+        let mut neighbours: Vec<&T> = Vec::with_capacity(8);
+        let next: Vec<Vec<T>> = (0..self.grid.len())
+            .map(|i| {
+                (0..self.grid[i].len())
+                    .map(|j| next_value(self, neighbourhood, i, j, &mut neighbours, &rule))
+                    .collect()
+            })
+            .collect();
+        self.grid = next;
+    }
+}
+
+// Cellular automaton over a grid. The next generation is written into a second
+// buffer which is then swapped in, so nothing is reallocated between steps
+pub struct Automaton<T> {
+    current: Grid<T>,
+    next: Grid<T>,
+    neighbourhood: Neighbourhood,
+    generation: usize,
+}
+
+impl<T> Automaton<T>
+where
+    T: Clone,
+{
+    pub fn new(grid: Grid<T>, neighbourhood: Neighbourhood) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            neighbourhood,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // rule gets the cell and its in-bounds neighbours and returns the cell's next value
+    pub fn step<F>(&mut self, rule: F)
+    where
+        F: Fn(&T, &[&T]) -> T,
+    {
+        fill_rows(
+            &self.current,
+            self.neighbourhood,
+            0,
+            &mut self.next.grid,
+            &rule,
+        );
+        self.swap();
+    }
+
+    // Same as step but the rows are split between threads
+    pub fn step_parallel<F>(&mut self, rule: F, threads: usize)
+    where
+        T: Send + Sync,
+        F: Fn(&T, &[&T]) -> T + Sync,
+    {
+        if threads == 0 {
+            panic!("Need at least one thread");
+        }
+        let chunk_size = self.current.grid.len().div_ceil(threads).max(1);
+        let current = &self.current;
+        let neighbourhood = self.neighbourhood;
+        let rule = &rule;
+        thread::scope(|scope| {
+            for (chunk, rows) in self.next.grid.chunks_mut(chunk_size).enumerate() {
+                scope.spawn(move || {
+                    fill_rows(current, neighbourhood, chunk * chunk_size, rows, rule);
+                });
+            }
+        });
+        self.swap();
+    }
+
+    pub fn run<F>(&mut self, generations: usize, rule: F)
+    where
+        F: Fn(&T, &[&T]) -> T,
+    {
+        for _ in 0..generations {
+            self.step(&rule);
+        }
+    }
+
+    // Number of generations that changed the grid, None if still changing after
+    // max_generations of them. The last step, which changes nothing, isn't counted
+    pub fn run_until_stable<F>(&mut self, max_generations: usize, rule: F) -> Option<usize>
+    where
+        T: PartialEq,
+        F: Fn(&T, &[&T]) -> T,
+    {
+        for changed in 0..=max_generations {
+            self.step(&rule);
+            if self.current == self.next {
+                return Some(changed);
+            }
+        }
+        None
+    }
+
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+    }
+}
+
+// Writes the next value of every cell in rows, which start at first_row of the grid
+fn fill_rows<'a, T, F>(
+    grid: &'a Grid<T>,
+    neighbourhood: Neighbourhood,
+    first_row: usize,
+    rows: &mut [Vec<T>],
+    rule: &F,
+) where
+    F: Fn(&T, &[&T]) -> T,
+{
+    let mut neighbours: Vec<&'a T> = Vec::with_capacity(8);
+    for (offset, row) in rows.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            *cell = next_value(
+                grid,
+                neighbourhood,
+                first_row + offset,
+                j,
+                &mut neighbours,
+                rule,
+            );
+        }
+    }
+}
+
+// neighbours is scratch space shared between cells to save allocating
+fn next_value<'a, T, F>(
+    grid: &'a Grid<T>,
+    neighbourhood: Neighbourhood,
+    i: usize,
+    j: usize,
+    neighbours: &mut Vec<&'a T>,
+    rule: &F,
+) -> T
+where
+    F: Fn(&T, &[&T]) -> T,
+{
+    let height = grid.grid.len() as i32;
+    let width = grid.grid[i].len() as i32;
+    neighbours.clear();
+    for (di, dj) in neighbourhood.offsets() {
+        let (ni, nj) = (i as i32 + di, j as i32 + dj);
+        if ni >= 0 && ni < height && nj >= 0 && nj < width {
+            neighbours.push(&grid.grid[ni as usize][nj as usize]);
+        }
+    }
+    rule(&grid.grid[i][j], neighbours)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cell: &char, neighbours: &[&char]) -> char {
+        let alive = neighbours.iter().filter(|c| ***c == '#').count();
+        match (*cell, alive) {
+            ('#', 2) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn test_blinker() {
+        let grid: Grid<char> = Grid::new(
+            ".....
+..#..
+..#..
+..#..
+.....",
+        );
+        let mut automaton = Automaton::new(grid.clone(), Neighbourhood::Moore);
+        automaton.step(life);
+        assert_eq!(
+            automaton.grid().to_string(),
+            ".....\n.....\n.###.\n.....\n....."
+        );
+        automaton.run(3, life);
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(*automaton.grid(), grid);
+        assert_eq!(automaton.run_until_stable(10, life), None);
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        // Fills outwards one cell per generation
+        let grid: Grid<char> = Grid::new("#....");
+        let mut automaton = Automaton::new(grid, Neighbourhood::VonNeumann);
+        let spread = |cell: &char, neighbours: &[&char]| {
+            if neighbours.contains(&&'#') {
+                '#'
+            } else {
+                *cell
+            }
+        };
+        assert_eq!(automaton.run_until_stable(10, spread), Some(4));
+        assert_eq!(automaton.run_until_stable(0, spread), Some(0));
+        assert_eq!(automaton.into_grid().to_string(), "#####");
+
+        let mut limited = Automaton::new(Grid::<char>::new("#...."), Neighbourhood::VonNeumann);
+        assert_eq!(limited.run_until_stable(2, spread), None);
+        assert_eq!(limited.run_until_stable(1, spread), Some(1));
+    }
+
+    #[test]
+    fn test_grid_step() {
+        let mut grid: Grid<char> = Grid::new(
+            "...
+###
+...",
+        );
+        grid.step(Neighbourhood::Moore, life);
+        assert_eq!(grid.to_string(), ".#.\n.#.\n.#.");
+        let mut heights: Grid<i32> = Grid::new("102");
+        heights.step(Neighbourhood::VonNeumann, |height, neighbours| {
+            neighbours
+                .iter()
+                .map(|other| **other)
+                .max()
+                .unwrap()
+                .max(*height)
+        });
+        assert_eq!(heights, Grid::from_rows(vec![vec![1, 2, 2]]));
+    }
+
+    #[test]
+    fn test_step_parallel() {
+        let grid: Grid<char> = Grid::new(
+            ".#..#..
+..#...#
+###.###
+.......
+..##...
+.##....
+..#....",
+        );
+        let mut serial = Automaton::new(grid.clone(), Neighbourhood::Moore);
+        let mut parallel = Automaton::new(grid, Neighbourhood::Moore);
+        for _ in 0..5 {
+            serial.step(life);
+            parallel.step_parallel(life, 3);
+        }
+        assert_eq!(serial.grid(), parallel.grid());
+    }
+}
@@ -0,0 +1,260 @@
+use crate::Point2D;
+use std::ops::{BitAnd, BitOr};
+
+// One bit per cell, addressed with readable coordinates like Grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitGrid {
+    width: i32,
+    height: i32,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        if width <= 0 || height <= 0 {
+            panic!("BitGrid must have a positive size");
+        }
+        Self {
+            width,
+            height,
+            words: vec![0; (width as usize * height as usize).div_ceil(64)],
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    pub fn in_bounds(&self, point: &Point2D) -> bool {
+        point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
+    }
+
+    // Returns true if the bit wasn't already set
+    pub fn set(&mut self, point: &Point2D) -> bool {
+        let (word, mask) = self.locate(point);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        !was_set
+    }
+
+    pub fn test(&self, point: &Point2D) -> bool {
+        let (word, mask) = self.locate(point);
+        self.words[word] & mask != 0
+    }
+
+    pub fn clear(&mut self, point: &Point2D) {
+        let (word, mask) = self.locate(point);
+        self.words[word] &= !mask;
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // Set points, bottom row first
+    pub fn iter(&self) -> impl Iterator<Item = Point2D> + '_ {
+        (0..self.width * self.height)
+            .filter(|index| self.words[*index as usize / 64] & (1 << (index % 64)) != 0)
+            .map(|index| Point2D::new(&(index % self.width, index / self.width)))
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.check_size(other);
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word |= other_word;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.check_size(other);
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word &= other_word;
+        }
+    }
+
+    fn check_size(&self, other: &BitGrid) {
+        if self.width != other.width || self.height != other.height {
+            panic!("BitGrids are not the same size");
+        }
+    }
+
+    fn locate(&self, point: &Point2D) -> (usize, u64) {
+        if !self.in_bounds(point) {
+            panic!("Point is outside the BitGrid");
+        }
+        let index = (point.y * self.width + point.x) as usize;
+        (index / 64, 1 << (index % 64))
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: Self) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: Self) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+}
+
+// Four bits per cell, one for each heading, for loop detection on walks
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionalBitGrid {
+    width: i32,
+    height: i32,
+    words: Vec<u64>,
+}
+
+impl DirectionalBitGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        if width <= 0 || height <= 0 {
+            panic!("DirectionalBitGrid must have a positive size");
+        }
+        Self {
+            width,
+            height,
+            words: vec![0; (width as usize * height as usize).div_ceil(16)],
+        }
+    }
+
+    pub fn in_bounds(&self, point: &Point2D) -> bool {
+        point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
+    }
+
+    // Returns true if this cell hadn't been seen with this heading before
+    pub fn set(&mut self, point: &Point2D, direction: usize) -> bool {
+        let (word, mask) = self.locate(point, direction);
+        let was_set = self.words[word] & mask != 0;
+        self.words[word] |= mask;
+        !was_set
+    }
+
+    pub fn test(&self, point: &Point2D, direction: usize) -> bool {
+        let (word, mask) = self.locate(point, direction);
+        self.words[word] & mask != 0
+    }
+
+    // Seen with any heading
+    pub fn any(&self, point: &Point2D) -> bool {
+        let (word, shift) = self.cell(point);
+        (self.words[word] >> shift) & 0b1111 != 0
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    // Number of cells seen with any heading
+    pub fn count_cells(&self) -> usize {
+        self.cells().count()
+    }
+
+    // Cells seen with any heading, bottom row first
+    pub fn cells(&self) -> impl Iterator<Item = Point2D> + '_ {
+        (0..self.width * self.height)
+            .map(|index| Point2D::new(&(index % self.width, index / self.width)))
+            .filter(|point| self.any(point))
+    }
+
+    // Collapses the headings into a plain visited mask
+    pub fn to_bit_grid(&self) -> BitGrid {
+        let mut result = BitGrid::new(self.width, self.height);
+        for point in self.cells() {
+            result.set(&point);
+        }
+        result
+    }
+
+    fn cell(&self, point: &Point2D) -> (usize, usize) {
+        if !self.in_bounds(point) {
+            panic!("Point is outside the DirectionalBitGrid");
+        }
+        let index = (point.y * self.width + point.x) as usize;
+        (index / 16, (index % 16) * 4)
+    }
+
+    fn locate(&self, point: &Point2D, direction: usize) -> (usize, u64) {
+        if direction >= 4 {
+            panic!("Direction index must be below 4");
+        }
+        let (word, shift) = self.cell(point);
+        (word, 1 << (shift + direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_grid() {
+        let mut bits = BitGrid::new(10, 10);
+        assert!(bits.set(&Point2D::new(&(3, 7))));
+        assert!(!bits.set(&Point2D::new(&(3, 7))));
+        assert!(bits.set(&Point2D::new(&(9, 9))));
+        assert!(bits.test(&Point2D::new(&(3, 7))));
+        assert!(!bits.test(&Point2D::new(&(7, 3))));
+        assert_eq!(bits.count(), 2);
+
+        let points: Vec<Point2D> = bits.iter().collect();
+        assert_eq!(points, vec![Point2D::new(&(3, 7)), Point2D::new(&(9, 9))]);
+
+        bits.clear(&Point2D::new(&(3, 7)));
+        assert_eq!(bits.count(), 1);
+    }
+
+    #[test]
+    fn test_bit_grid_set_operations() {
+        let mut first = BitGrid::new(3, 3);
+        let mut second = BitGrid::new(3, 3);
+        first.set(&Point2D::new(&(0, 0)));
+        first.set(&Point2D::new(&(1, 1)));
+        second.set(&Point2D::new(&(1, 1)));
+        second.set(&Point2D::new(&(2, 2)));
+
+        assert_eq!((&first | &second).count(), 3);
+        let both = &first & &second;
+        assert_eq!(
+            both.iter().collect::<Vec<Point2D>>(),
+            vec![Point2D::new(&(1, 1))]
+        );
+    }
+
+    #[test]
+    fn test_directional_bit_grid() {
+        let mut seen = DirectionalBitGrid::new(5, 5);
+        let point = Point2D::new(&(4, 2));
+        assert!(!seen.any(&point));
+        assert!(seen.set(&point, 0));
+        assert!(seen.set(&point, 3));
+        assert!(!seen.set(&point, 3));
+        assert!(seen.test(&point, 0));
+        assert!(!seen.test(&point, 1));
+        assert!(seen.any(&point));
+
+        seen.set(&Point2D::new(&(0, 3)), 2);
+        assert_eq!(seen.count_cells(), 2);
+        assert_eq!(seen.to_bit_grid().count(), 2);
+    }
+}
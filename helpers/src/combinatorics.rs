@@ -0,0 +1,254 @@
+// Lazy combinatorial iterators. Nothing allocates per item: pairs borrow from the
+// slice, and the larger selections are small Copy views that are read back with
+// get or iter
+
+// Every ordered pair of different positions, so both (a, b) and (b, a)
+pub fn pairs<T>(items: &[T]) -> impl Iterator<Item = (&T, &T)> + '_ {
+    (0..items.len()).flat_map(move |i| {
+        (0..items.len())
+            .filter(move |j| *j != i)
+            .map(move |j| (&items[i], &items[j]))
+    })
+}
+
+// Each pair of positions once, in slice order
+pub fn unordered_pairs<T>(items: &[T]) -> impl Iterator<Item = (&T, &T)> + '_ {
+    (0..items.len()).flat_map(move |i| (i + 1..items.len()).map(move |j| (&items[i], &items[j])))
+}
+
+// Every way to pick k items, keeping slice order within each pick. Needs at most
+// 64 items
+pub fn combinations<T>(items: &[T], k: usize) -> impl Iterator<Item = Selection<'_, T>> {
+    if items.len() > 64 {
+        panic!("Combinations only support up to 64 items");
+    }
+    let limit: u128 = 1 << items.len();
+    let first: Option<u64> = if k <= items.len() {
+        Some(((1u128 << k) - 1) as u64)
+    } else {
+        None
+    };
+    // Gosper's hack steps to the next larger mask with the same number of bits
+    std::iter::successors(first, move |mask| {
+        if *mask == 0 {
+            return None;
+        }
+        let lowest = mask & mask.wrapping_neg();
+        let ripple = *mask as u128 + lowest as u128;
+        let next = (((ripple ^ *mask as u128) >> 2) / lowest as u128) | ripple;
+        if next < limit {
+            Some(next as u64)
+        } else {
+            None
+        }
+    })
+    .map(move |mask| Selection { items, mask })
+}
+
+// Every ordering of the whole slice in lexicographic order of positions. Needs at
+// most 20 items
+pub fn permutations<T>(items: &[T]) -> impl Iterator<Item = Arrangement<'_, T>> {
+    if items.len() > 20 {
+        panic!("Permutations only support up to 20 items");
+    }
+    (0..factorial(items.len())).map(move |index| Arrangement { items, index })
+}
+
+// Every length n sequence drawn from the alphabet with repeats, like an odometer
+// with the last position turning fastest
+pub fn product<T>(alphabet: &[T], n: usize) -> impl Iterator<Item = Choice<'_, T>> {
+    let count = (0..n).try_fold(1u64, |acc, _| acc.checked_mul(alphabet.len() as u64));
+    let Some(count) = count else {
+        panic!("Product has more than u64::MAX items");
+    };
+    (0..count).map(move |index| Choice {
+        alphabet,
+        index,
+        len: n,
+    })
+}
+
+fn factorial(n: usize) -> u64 {
+    (1..=n as u64).product()
+}
+
+// Items picked by combinations, held as a bit mask over the slice
+#[derive(Debug)]
+pub struct Selection<'a, T> {
+    items: &'a [T],
+    mask: u64,
+}
+
+// Derived Copy would wrongly require T: Copy
+impl<T> Clone for Selection<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Selection<'_, T> {}
+
+impl<'a, T> Selection<'a, T> {
+    pub fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let (items, mask) = (self.items, self.mask);
+        (0..items.len())
+            .filter(move |i| mask & (1 << i) != 0)
+            .map(move |i| &items[i])
+    }
+}
+
+// One ordering from permutations, held as its index in lexicographic order
+#[derive(Debug)]
+pub struct Arrangement<'a, T> {
+    items: &'a [T],
+    index: u64,
+}
+
+impl<T> Clone for Arrangement<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Arrangement<'_, T> {}
+
+impl<'a, T> Arrangement<'a, T> {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // Decodes the index as a factorial base number, each digit picks one of the
+    // positions not used yet
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let items = self.items;
+        let mut remainder = self.index;
+        let mut used: u32 = 0;
+        (0..items.len()).map(move |position| {
+            let radix = factorial(items.len() - 1 - position);
+            let digit = remainder / radix;
+            remainder %= radix;
+            let chosen = (0..items.len())
+                .filter(|i| used & (1 << i) == 0)
+                .nth(digit as usize)
+                .expect("Digit is below the number of unused positions");
+            used |= 1 << chosen;
+            &items[chosen]
+        })
+    }
+}
+
+// One sequence from product, held as its index in base alphabet.len()
+#[derive(Debug)]
+pub struct Choice<'a, T> {
+    alphabet: &'a [T],
+    index: u64,
+    len: usize,
+}
+
+impl<T> Clone for Choice<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Choice<'_, T> {}
+
+impl<'a, T> Choice<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, position: usize) -> &'a T {
+        if position >= self.len {
+            panic!("Position is past the end of the Choice");
+        }
+        let base = self.alphabet.len() as u64;
+        let digit = self.index / base.pow((self.len - 1 - position) as u32) % base;
+        &self.alphabet[digit as usize]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let choice = *self;
+        (0..self.len).map(move |position| choice.get(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<'a>(items: impl Iterator<Item = &'a char>) -> String {
+        items.collect()
+    }
+
+    #[test]
+    fn test_pairs() {
+        let items = [1, 2, 3];
+        let ordered: Vec<(i32, i32)> = pairs(&items).map(|(a, b)| (*a, *b)).collect();
+        assert_eq!(
+            ordered,
+            vec![(1, 2), (1, 3), (2, 1), (2, 3), (3, 1), (3, 2)]
+        );
+        let unordered: Vec<(i32, i32)> = unordered_pairs(&items).map(|(a, b)| (*a, *b)).collect();
+        assert_eq!(unordered, vec![(1, 2), (1, 3), (2, 3)]);
+        assert_eq!(pairs(&[1]).count(), 0);
+        assert_eq!(unordered_pairs::<i32>(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_combinations() {
+        let items = ['a', 'b', 'c', 'd'];
+        let picks: Vec<String> = combinations(&items, 2)
+            .map(|pick| collect(pick.iter()))
+            .collect();
+        assert_eq!(picks, vec!["ab", "ac", "bc", "ad", "bd", "cd"]);
+        assert_eq!(combinations(&items, 0).count(), 1);
+        assert_eq!(combinations(&items, 4).count(), 1);
+        assert_eq!(combinations(&items, 5).count(), 0);
+        assert_eq!(combinations(&[0; 64], 2).count(), 2016);
+        assert_eq!(combinations(&[0; 64], 64).count(), 1);
+    }
+
+    #[test]
+    fn test_permutations() {
+        let items = ['a', 'b', 'c'];
+        let orders: Vec<String> = permutations(&items)
+            .map(|order| collect(order.iter()))
+            .collect();
+        assert_eq!(orders, vec!["abc", "acb", "bac", "bca", "cab", "cba"]);
+        assert_eq!(permutations(&[0; 6]).count(), 720);
+        assert_eq!(permutations::<i32>(&[]).count(), 1);
+    }
+
+    #[test]
+    fn test_product() {
+        let operators = ['+', '*'];
+        let sequences: Vec<String> = product(&operators, 3)
+            .map(|choice| collect(choice.iter()))
+            .collect();
+        assert_eq!(
+            sequences,
+            vec!["+++", "++*", "+*+", "+**", "*++", "*+*", "**+", "***"]
+        );
+        let last = product(&operators, 3).last().unwrap();
+        assert_eq!(last.get(1), &'*');
+        assert_eq!(product(&['a', 'b', 'c'], 4).count(), 81);
+        assert_eq!(product(&operators, 0).count(), 1);
+    }
+}
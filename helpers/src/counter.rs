@@ -0,0 +1,174 @@
+use crate::hash::FastMap;
+use std::cmp::Reverse;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Sub};
+
+// Multiset that tracks how many times each value has been added. Values with a
+// count of zero are never stored
+#[derive(Debug, Clone)]
+pub struct Counter<T> {
+    counts: FastMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash> PartialEq for Counter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self {
+            counts: FastMap::default(),
+        }
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_many(item, 1);
+    }
+
+    pub fn add_many(&mut self, item: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(item).or_insert(0) += count;
+        }
+    }
+
+    // Stops at zero and forgets the item
+    pub fn remove_many(&mut self, item: &T, count: usize) {
+        if let Some(current) = self.counts.get_mut(item) {
+            if *current > count {
+                *current -= count;
+            } else {
+                self.counts.remove(item);
+            }
+        }
+    }
+
+    // 0 for items that were never added
+    pub fn count(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    // Number of distinct items
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    // Sum of every count
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    // Arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, count)| (item, *count))
+    }
+
+    // Highest count first, ties in arbitrary order
+    pub fn iter_by_count(&self) -> impl Iterator<Item = (&T, usize)> {
+        let mut items: Vec<(&T, usize)> = self.iter().collect();
+        items.sort_by_key(|(_, count)| Reverse(*count));
+        items.into_iter()
+    }
+
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        self.iter_by_count().take(n).collect()
+    }
+}
+
+impl<T: Eq + Hash + Clone> Add for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn add(self, other: Self) -> Counter<T> {
+        let mut result = self.clone();
+        result += other;
+        result
+    }
+}
+
+impl<T: Eq + Hash + Clone> AddAssign<&Counter<T>> for Counter<T> {
+    fn add_assign(&mut self, other: &Counter<T>) {
+        for (item, count) in other.iter() {
+            self.add_many(item.clone(), count);
+        }
+    }
+}
+
+// Counts stop at zero rather than going negative
+impl<T: Eq + Hash + Clone> Sub for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn sub(self, other: Self) -> Counter<T> {
+        let mut result = self.clone();
+        for (item, count) in other.iter() {
+            result.remove_many(item, count);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.count(&'a'), 5);
+        assert_eq!(counter.count(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.most_common(1), vec![(&'a', 5)]);
+        assert_eq!(counter.most_common(10).len(), 5);
+
+        let counts: Vec<usize> = counter.iter_by_count().map(|(_, count)| count).collect();
+        assert_eq!(counts, vec![5, 2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut first: Counter<i32> = [1, 1, 2, 3].into_iter().collect();
+        let second: Counter<i32> = [1, 2, 2, 4].into_iter().collect();
+
+        let sum = &first + &second;
+        assert_eq!(sum.count(&1), 3);
+        assert_eq!(sum.count(&2), 3);
+        assert_eq!(sum.total(), 8);
+
+        let difference = &first - &second;
+        assert_eq!(difference, [1, 3].into_iter().collect());
+        assert_eq!(difference.count(&2), 0);
+        assert_eq!(difference.len(), 2);
+
+        first.add_many(7, 10);
+        first.remove_many(&1, 5);
+        first.add_many(8, 0);
+        assert_eq!(first, Counter::from_iter([2, 3].into_iter().chain([7; 10])));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::Point2D;
+use std::fmt;
+use std::str::FromStr;
+
+// Compass headings in readable coordinates, so Up is +y
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+}
+
+impl Direction {
+    pub const CARDINALS: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub const DIAGONALS: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    // Clockwise from Up
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn offset(&self) -> Point2D {
+        match self {
+            Direction::Up => Point2D::new(&(0, 1)),
+            Direction::Right => Point2D::new(&(1, 0)),
+            Direction::Down => Point2D::new(&(0, -1)),
+            Direction::Left => Point2D::new(&(-1, 0)),
+            Direction::UpRight => Point2D::new(&(1, 1)),
+            Direction::DownRight => Point2D::new(&(1, -1)),
+            Direction::DownLeft => Point2D::new(&(-1, -1)),
+            Direction::UpLeft => Point2D::new(&(-1, 1)),
+        }
+    }
+
+    // Quarter turn clockwise
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::UpRight => Direction::DownRight,
+            Direction::DownRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpLeft,
+            Direction::UpLeft => Direction::UpRight,
+        }
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.turn_right().turn_right().turn_right()
+    }
+
+    pub fn reverse(&self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        Direction::DIAGONALS.contains(self)
+    }
+
+    // Cardinals are 0 to 3 so they fit in a DirectionalBitGrid, diagonals are 4 to 7
+    pub fn index(&self) -> usize {
+        match self {
+            Direction::Up => 0,
+            Direction::Right => 1,
+            Direction::Down => 2,
+            Direction::Left => 3,
+            Direction::UpRight => 4,
+            Direction::DownRight => 5,
+            Direction::DownLeft => 6,
+            Direction::UpLeft => 7,
+        }
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            0..=3 => Direction::CARDINALS[index],
+            4..=7 => Direction::DIAGONALS[index - 4],
+            _ => panic!("Direction index must be below 8"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseDirectionError;
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid string for Direction")
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+// Arrows (^>v<), UDLR or compass points (NESW, NE etc.)
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "^" | "U" | "u" | "N" | "n" => Ok(Direction::Up),
+            ">" | "R" | "r" | "E" | "e" => Ok(Direction::Right),
+            "v" | "D" | "d" | "S" | "s" => Ok(Direction::Down),
+            "<" | "L" | "l" | "W" | "w" => Ok(Direction::Left),
+            "NE" | "ne" => Ok(Direction::UpRight),
+            "SE" | "se" => Ok(Direction::DownRight),
+            "SW" | "sw" => Ok(Direction::DownLeft),
+            "NW" | "nw" => Ok(Direction::UpLeft),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.encode_utf8(&mut [0; 4]).parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+            assert_eq!(
+                direction.turn_right().offset(),
+                direction.offset().rotate_cw()
+            );
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::UpLeft.turn_left(), Direction::DownLeft);
+    }
+
+    #[test]
+    fn test_index() {
+        for direction in Direction::ALL {
+            assert_eq!(Direction::from_index(direction.index()), direction);
+        }
+        assert!(Direction::CARDINALS.iter().all(|d| d.index() < 4));
+        assert!(Direction::DIAGONALS.iter().all(|d| d.is_diagonal()));
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = ["^", "v", "<", ">", "U", "l", "N", "e", "SW", "nw"];
+        let expected = [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+            Direction::DownLeft,
+            Direction::UpLeft,
+        ];
+        for (i, input_val) in input.iter().enumerate() {
+            assert_eq!(input_val.parse::<Direction>(), Ok(expected[i]));
+        }
+        assert_eq!("x".parse::<Direction>(), Err(ParseDirectionError));
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::hash::FastMap;
+use crate::{Grid, Rgb};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+const MAX_CODE_SIZE: u8 = 12;
+const MAX_CODES: u16 = 1 << MAX_CODE_SIZE;
+
+// Records grid states as palette indices and encodes them as a looping GIF
+pub struct GifRecorder {
+    palette: Vec<Rgb>,
+    scale: usize,
+    // Hundredths of a second per frame
+    delay: u16,
+    width: usize,
+    height: usize,
+    frames: Vec<Vec<u8>>,
+}
+
+impl GifRecorder {
+    pub fn new(palette: Vec<Rgb>, scale: usize, delay: u16) -> Self {
+        if palette.is_empty() || palette.len() > 256 {
+            panic!("Palette must have between 1 and 256 colours");
+        }
+        if scale == 0 {
+            panic!("Scale must be at least 1");
+        }
+        Self {
+            palette,
+            scale,
+            delay,
+            width: 0,
+            height: 0,
+            frames: vec![],
+        }
+    }
+
+    pub fn record<T, F>(&mut self, grid: &Grid<T>, colour_index: F)
+    where
+        F: Fn(&T) -> u8,
+    {
+        let width = grid.width() as usize * self.scale;
+        let height = grid.height() as usize * self.scale;
+        if self.frames.is_empty() {
+            if width > u16::MAX as usize || height > u16::MAX as usize {
+                panic!("Grid is too large for a GIF");
+            }
+            self.width = width;
+            self.height = height;
+        } else if width != self.width || height != self.height {
+            panic!("Frame size doesn't match the first frame");
+        }
+
+        let mut pixels: Vec<u8> = Vec::with_capacity(width * height);
+        for row in grid.iter() {
+            let mut line: Vec<u8> = Vec::with_capacity(width);
+            for value in row {
+                let index = colour_index(value);
+                if index as usize >= self.palette.len() {
+                    panic!("Colour index is outside the palette");
+                }
+                line.extend(std::iter::repeat_n(index, self.scale));
+            }
+            for _ in 0..self.scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        self.frames.push(pixels);
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "No frames recorded",
+            ));
+        }
+
+        // Colour table holds 2^(table_bits + 1) entries
+        let mut table_bits: u8 = 0;
+        while (2usize << table_bits) < self.palette.len() {
+            table_bits += 1;
+        }
+        let min_code_size = (table_bits + 1).max(2);
+
+        writer.write_all(b"GIF89a")?;
+        writer.write_all(&(self.width as u16).to_le_bytes())?;
+        writer.write_all(&(self.height as u16).to_le_bytes())?;
+        writer.write_all(&[0x80 | (table_bits << 4) | table_bits, 0, 0])?;
+        for i in 0..(2usize << table_bits) {
+            writer.write_all(self.palette.get(i).unwrap_or(&[0, 0, 0]))?;
+        }
+
+        // Loop forever
+        writer.write_all(&[0x21, 0xFF, 0x0B])?;
+        writer.write_all(b"NETSCAPE2.0")?;
+        writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        for frame in &self.frames {
+            writer.write_all(&[0x21, 0xF9, 0x04, 0x00])?;
+            writer.write_all(&self.delay.to_le_bytes())?;
+            writer.write_all(&[0x00, 0x00])?;
+
+            writer.write_all(&[0x2C, 0, 0, 0, 0])?;
+            writer.write_all(&(self.width as u16).to_le_bytes())?;
+            writer.write_all(&(self.height as u16).to_le_bytes())?;
+            writer.write_all(&[0x00, min_code_size])?;
+
+            for block in lzw_encode(frame, min_code_size).chunks(255) {
+                writer.write_all(&[block.len() as u8])?;
+                writer.write_all(block)?;
+            }
+            writer.write_all(&[0x00])?;
+        }
+        writer.write_all(&[0x3B])
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+}
+
+// Packs variable width codes least significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: vec![],
+            buffer: 0,
+            bits: 0,
+        }
+    }
+
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear_code: u16 = 1 << min_code_size;
+    let end_code = clear_code + 1;
+    let mut writer = BitWriter::new();
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end_code + 1;
+    let mut table: FastMap<(u16, u8), u16> = FastMap::default();
+
+    writer.write(clear_code, code_size);
+    let Some((first, rest)) = indices.split_first() else {
+        writer.write(end_code, code_size);
+        return writer.finish();
+    };
+
+    let mut current = *first as u16;
+    for &index in rest {
+        if let Some(&code) = table.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+        writer.write(current, code_size);
+        // The decoder runs one entry behind, so widen before adding the new entry
+        if next_code >= (1 << code_size) && code_size < MAX_CODE_SIZE {
+            code_size += 1;
+        }
+        if next_code < MAX_CODES {
+            table.insert((current, index), next_code);
+            next_code += 1;
+        } else {
+            writer.write(clear_code, code_size);
+            table.clear();
+            next_code = end_code + 1;
+            code_size = min_code_size + 1;
+        }
+        current = index as u16;
+    }
+    writer.write(current, code_size);
+    if next_code >= (1 << code_size) && code_size < MAX_CODE_SIZE {
+        code_size += 1;
+    }
+    writer.write(end_code, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference decoder to check the encoder against
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear_code: u16 = 1 << min_code_size;
+        let end_code = clear_code + 1;
+        let mut result: Vec<u8> = vec![];
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut position = 0;
+
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear_code).map(|i| vec![i as u8]).collect();
+            table.push(vec![]);
+            table.push(vec![]);
+        };
+        reset(&mut table);
+
+        loop {
+            let mut code: u16 = 0;
+            for bit in 0..code_size {
+                let byte = bytes[position / 8];
+                code |= (((byte >> (position % 8)) & 1) as u16) << bit;
+                position += 1;
+            }
+            if code == clear_code {
+                reset(&mut table);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end_code {
+                return result;
+            }
+            let entry = match (&previous, table.get(code as usize)) {
+                (_, Some(entry)) => entry.clone(),
+                (Some(prev), None) => {
+                    let mut entry = prev.clone();
+                    entry.push(prev[0]);
+                    entry
+                }
+                (None, None) => panic!("Invalid first code"),
+            };
+            result.extend_from_slice(&entry);
+            if let Some(prev) = previous {
+                if table.len() < MAX_CODES as usize {
+                    let mut new_entry = prev.clone();
+                    new_entry.push(entry[0]);
+                    table.push(new_entry);
+                }
+            }
+            if table.len() >= (1 << code_size) && code_size < MAX_CODE_SIZE {
+                code_size += 1;
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let inputs: Vec<(Vec<u8>, u8)> = vec![
+            (vec![], 2),
+            (vec![1], 2),
+            (vec![0, 0, 0, 0, 0, 0, 0, 0], 2),
+            (vec![1, 2, 1, 2, 1, 2, 3, 3, 3, 0, 1], 2),
+            (
+                (0..20000).map(|i| ((i * 7 + i / 13) % 5) as u8).collect(),
+                3,
+            ),
+            (
+                (0..20000).map(|i| ((i * 31 + i / 7) % 256) as u8).collect(),
+                8,
+            ),
+        ];
+        for (indices, min_code_size) in inputs {
+            let encoded = lzw_encode(&indices, min_code_size);
+            assert_eq!(lzw_decode(&encoded, min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn test_gif_layout() {
+        let grid: Grid<char> = Grid::new(
+            "#.
+.#",
+        );
+        let mut recorder = GifRecorder::new(vec![[0, 0, 0], [255, 255, 255], [255, 0, 0]], 2, 10);
+        recorder.record(&grid, |c| if *c == '#' { 1 } else { 0 });
+        recorder.record(&grid, |c| if *c == '#' { 2 } else { 0 });
+        assert_eq!(recorder.frame_count(), 2);
+
+        let mut output: Vec<u8> = vec![];
+        recorder.write(&mut output).unwrap();
+        assert_eq!(&output[0..6], b"GIF89a");
+        assert_eq!(&output[6..10], &[4, 0, 4, 0]);
+        // Three colours round up to a four entry table
+        assert_eq!(output[10], 0x80 | 0x10 | 0x01);
+        assert_eq!(
+            &output[13..25],
+            &[0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(*output.last().unwrap(), 0x3B);
+    }
+
+    #[test]
+    fn test_gif_without_frames() {
+        let recorder = GifRecorder::new(vec![[0, 0, 0]], 1, 10);
+        let mut output: Vec<u8> = vec![];
+        assert!(recorder.write(&mut output).is_err());
+    }
+}
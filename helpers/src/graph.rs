@@ -0,0 +1,708 @@
+use crate::hash::{FastMap, FastSet};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// Directed graph keyed by node value. Nodes keep their insertion order, which
+// makes every traversal below deterministic
+#[derive(Debug, Clone)]
+pub struct DiGraph<N> {
+    ids: FastMap<N, usize>,
+    nodes: Vec<N>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<N: Clone + Eq + Hash> Default for DiGraph<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for DiGraph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(iter: I) -> Self {
+        let mut graph = DiGraph::new();
+        for (from, to) in iter {
+            graph.add_edge(&from, &to);
+        }
+        graph
+    }
+}
+
+impl<N: Clone + Eq + Hash> DiGraph<N> {
+    pub fn new() -> Self {
+        Self {
+            ids: FastMap::default(),
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    // Does nothing if the node is already present
+    pub fn add_node(&mut self, node: &N) {
+        self.intern(node);
+    }
+
+    // Adds either end if missing, repeated edges are only stored once
+    pub fn add_edge(&mut self, from: &N, to: &N) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+        if !self.edges[from].contains(&to) {
+            self.edges[from].push(to);
+        }
+    }
+
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.ids.contains_key(node)
+    }
+
+    pub fn contains_edge(&self, from: &N, to: &N) -> bool {
+        match (self.ids.get(from), self.ids.get(to)) {
+            (Some(from), Some(to)) => self.edges[*from].contains(to),
+            _ => false,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(|targets| targets.len()).sum()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> + '_ {
+        let targets: &[usize] = match self.ids.get(node) {
+            Some(id) => &self.edges[*id],
+            None => &[],
+        };
+        targets.iter().map(|id| &self.nodes[*id])
+    }
+
+    // Only the given nodes (those present in the graph) and the edges between them
+    pub fn subgraph(&self, nodes: &[N]) -> DiGraph<N> {
+        let mut result = DiGraph::new();
+        for node in nodes.iter().filter(|node| self.contains_node(node)) {
+            result.add_node(node);
+        }
+        for node in nodes.iter().filter(|node| self.contains_node(node)) {
+            for target in self.successors(node) {
+                if result.contains_node(target) {
+                    result.add_edge(node, target);
+                }
+            }
+        }
+        result
+    }
+
+    // Every node reachable by following one or more edges
+    pub fn reachable_from(&self, node: &N) -> HashSet<N> {
+        let mut seen: Vec<bool> = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = match self.ids.get(node) {
+            Some(id) => self.edges[*id].clone(),
+            None => vec![],
+        };
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            stack.extend(self.edges[id].iter().filter(|next| !seen[**next]));
+        }
+        (0..self.nodes.len())
+            .filter(|id| seen[*id])
+            .map(|id| self.nodes[id].clone())
+            .collect()
+    }
+
+    pub fn can_reach(&self, from: &N, to: &N) -> bool {
+        self.reachable_from(from).contains(to)
+    }
+
+    // Kahn's algorithm, None if the graph has a cycle
+    pub fn topological_sort(&self) -> Option<Vec<N>> {
+        let mut in_degree: Vec<usize> = vec![0; self.nodes.len()];
+        for targets in &self.edges {
+            for target in targets {
+                in_degree[*target] += 1;
+            }
+        }
+        let mut queue: VecDeque<usize> = (0..self.nodes.len())
+            .filter(|id| in_degree[*id] == 0)
+            .collect();
+        let mut order: Vec<N> = Vec::with_capacity(self.nodes.len());
+        while let Some(id) = queue.pop_front() {
+            order.push(self.nodes[id].clone());
+            for target in &self.edges[id] {
+                in_degree[*target] -= 1;
+                if in_degree[*target] == 0 {
+                    queue.push_back(*target);
+                }
+            }
+        }
+        if order.len() == self.nodes.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    // Reverse post-order of a depth first search, None if the graph has a cycle
+    pub fn topological_sort_dfs(&self) -> Option<Vec<N>> {
+        match self.depth_first() {
+            Ok(mut post_order) => {
+                post_order.reverse();
+                Some(
+                    post_order
+                        .into_iter()
+                        .map(|id| self.nodes[id].clone())
+                        .collect(),
+                )
+            }
+            Err(_) => None,
+        }
+    }
+
+    // Nodes around one cycle in edge order, the last node has an edge back to the first
+    pub fn find_cycle(&self) -> Option<Vec<N>> {
+        match self.depth_first() {
+            Ok(_) => None,
+            Err(cycle) => Some(cycle.into_iter().map(|id| self.nodes[id].clone()).collect()),
+        }
+    }
+
+    pub fn is_acyclic(&self) -> bool {
+        self.depth_first().is_ok()
+    }
+
+    // Tarjan's algorithm. Components come out in reverse topological order, so
+    // nothing in a component has an edge to a component after it
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        let count = self.nodes.len();
+        let mut index: Vec<Option<usize>> = vec![None; count];
+        let mut low_link: Vec<usize> = vec![0; count];
+        let mut on_stack: Vec<bool> = vec![false; count];
+        let mut stack: Vec<usize> = vec![];
+        let mut next_index = 0;
+        let mut components: Vec<Vec<N>> = vec![];
+
+        for root in 0..count {
+            if index[root].is_some() {
+                continue;
+            }
+            // (node, position in its edge list) replaces the recursion
+            let mut work: Vec<(usize, usize)> = vec![(root, 0)];
+            while let Some((id, edge)) = work.pop() {
+                if edge == 0 {
+                    index[id] = Some(next_index);
+                    low_link[id] = next_index;
+                    next_index += 1;
+                    stack.push(id);
+                    on_stack[id] = true;
+                }
+                if let Some(target) = self.edges[id].get(edge) {
+                    work.push((id, edge + 1));
+                    match index[*target] {
+                        None => work.push((*target, 0)),
+                        Some(target_index) if on_stack[*target] => {
+                            low_link[id] = low_link[id].min(target_index);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                if let Some((parent, _)) = work.last() {
+                    low_link[*parent] = low_link[*parent].min(low_link[id]);
+                }
+                if Some(low_link[id]) == index[id] {
+                    let mut component: Vec<N> = vec![];
+                    loop {
+                        let member = stack.pop().expect("Node is on the stack");
+                        on_stack[member] = false;
+                        component.push(self.nodes[member].clone());
+                        if member == id {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    fn intern(&mut self, node: &N) -> usize {
+        if let Some(id) = self.ids.get(node) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node.clone());
+        self.edges.push(vec![]);
+        id
+    }
+
+    // Post-order of every node, or the ids around the first cycle found
+    fn depth_first(&self) -> Result<Vec<usize>, Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Active,
+            Done,
+        }
+        let mut marks: Vec<Mark> = vec![Mark::New; self.nodes.len()];
+        let mut post_order: Vec<usize> = Vec::with_capacity(self.nodes.len());
+
+        for root in 0..self.nodes.len() {
+            if marks[root] != Mark::New {
+                continue;
+            }
+            marks[root] = Mark::Active;
+            let mut path: Vec<(usize, usize)> = vec![(root, 0)];
+            while let Some((id, edge)) = path.pop() {
+                let Some(target) = self.edges[id].get(edge) else {
+                    marks[id] = Mark::Done;
+                    post_order.push(id);
+                    continue;
+                };
+                path.push((id, edge + 1));
+                match marks[*target] {
+                    Mark::New => {
+                        marks[*target] = Mark::Active;
+                        path.push((*target, 0));
+                    }
+                    Mark::Active => {
+                        let start = path
+                            .iter()
+                            .position(|(node, _)| node == target)
+                            .expect("Active nodes are on the path");
+                        return Err(path[start..].iter().map(|(node, _)| *node).collect());
+                    }
+                    Mark::Done => {}
+                }
+            }
+        }
+        Ok(post_order)
+    }
+}
+
+// Undirected graph over string names. Names are interned to ids on insertion
+// and given back as &str
+#[derive(Debug, Clone, Default)]
+pub struct UnGraph {
+    ids: FastMap<String, usize>,
+    names: Vec<String>,
+    adjacency: Vec<FastSet<usize>>,
+}
+
+impl<'a> FromIterator<(&'a str, &'a str)> for UnGraph {
+    fn from_iter<I: IntoIterator<Item = (&'a str, &'a str)>>(iter: I) -> Self {
+        let mut graph = UnGraph::new();
+        for (first, second) in iter {
+            graph.add_edge(first, second);
+        }
+        graph
+    }
+}
+
+impl UnGraph {
+    pub fn new() -> Self {
+        Self {
+            ids: FastMap::default(),
+            names: vec![],
+            adjacency: vec![],
+        }
+    }
+
+    // Lines of "a-b" style pairs with the given separator
+    pub fn from_edge_list(input: &str, separator: char) -> Self {
+        input
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| match line.split_once(separator) {
+                Some(pair) => pair,
+                None => panic!("Edge '{line}' has no separator"),
+            })
+            .collect()
+    }
+
+    pub fn add_node(&mut self, name: &str) {
+        self.intern(name);
+    }
+
+    // Self loops are ignored
+    pub fn add_edge(&mut self, first: &str, second: &str) {
+        let first = self.intern(first);
+        let second = self.intern(second);
+        if first != second {
+            self.adjacency[first].insert(second);
+            self.adjacency[second].insert(first);
+        }
+    }
+
+    pub fn contains_node(&self, name: &str) -> bool {
+        self.ids.contains_key(name)
+    }
+
+    pub fn contains_edge(&self, first: &str, second: &str) -> bool {
+        match (self.ids.get(first), self.ids.get(second)) {
+            (Some(first), Some(second)) => self.adjacency[*first].contains(second),
+            _ => false,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.adjacency
+            .iter()
+            .map(|edges| edges.len())
+            .sum::<usize>()
+            / 2
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(|name| name.as_str())
+    }
+
+    // Unordered
+    pub fn neighbours(&self, name: &str) -> impl Iterator<Item = &str> + '_ {
+        self.ids
+            .get(name)
+            .into_iter()
+            .flat_map(|id| self.adjacency[*id].iter())
+            .map(|id| self.names[*id].as_str())
+    }
+
+    pub fn degree(&self, name: &str) -> usize {
+        match self.ids.get(name) {
+            Some(id) => self.adjacency[*id].len(),
+            None => 0,
+        }
+    }
+
+    // None for an empty graph
+    pub fn degree_stats(&self) -> Option<DegreeStats> {
+        if self.names.is_empty() {
+            return None;
+        }
+        let mut histogram: BTreeMap<usize, usize> = BTreeMap::new();
+        for edges in &self.adjacency {
+            *histogram.entry(edges.len()).or_insert(0) += 1;
+        }
+        Some(DegreeStats {
+            nodes: self.names.len(),
+            total: self.edge_count() * 2,
+            histogram,
+        })
+    }
+
+    // Each component is sorted by name, components are ordered by their first name
+    pub fn connected_components(&self) -> Vec<Vec<&str>> {
+        let mut seen: Vec<bool> = vec![false; self.names.len()];
+        let mut components: Vec<Vec<&str>> = vec![];
+        for root in 0..self.names.len() {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut stack: Vec<usize> = vec![root];
+            let mut component: Vec<&str> = vec![];
+            while let Some(id) = stack.pop() {
+                component.push(&self.names[id]);
+                for next in &self.adjacency[id] {
+                    if !seen[*next] {
+                        seen[*next] = true;
+                        stack.push(*next);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components.sort();
+        components
+    }
+
+    // Every triangle once, names sorted within each and the list sorted overall
+    pub fn triangles(&self) -> Vec<[&str; 3]> {
+        let mut result: Vec<[&str; 3]> = vec![];
+        for (a, edges) in self.adjacency.iter().enumerate() {
+            for b in edges.iter().filter(|b| **b > a) {
+                for c in self.adjacency[*b].iter().filter(|c| **c > *b) {
+                    if edges.contains(c) {
+                        let mut triangle = [
+                            self.names[a].as_str(),
+                            self.names[*b].as_str(),
+                            self.names[*c].as_str(),
+                        ];
+                        triangle.sort();
+                        result.push(triangle);
+                    }
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    // Bron–Kerbosch with pivoting. Names are sorted, ties go to the first clique found
+    pub fn max_clique(&self) -> Vec<&str> {
+        let mut best: Vec<usize> = vec![];
+        let mut clique: Vec<usize> = vec![];
+        let candidates: FastSet<usize> = (0..self.names.len()).collect();
+        self.bron_kerbosch(&mut clique, candidates, FastSet::default(), &mut best);
+        let mut names: Vec<&str> = best.iter().map(|id| self.names[*id].as_str()).collect();
+        names.sort();
+        names
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: FastSet<usize>,
+        mut excluded: FastSet<usize>,
+        best: &mut Vec<usize>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() > best.len() {
+                *best = clique.clone();
+            }
+            return;
+        }
+        // Can't beat the best even taking every candidate
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+        let pivot = *candidates
+            .union(&excluded)
+            .max_by_key(|id| self.adjacency[**id].intersection(&candidates).count())
+            .expect("Candidates aren't empty");
+        let mut branches: Vec<usize> = candidates
+            .difference(&self.adjacency[pivot])
+            .copied()
+            .collect();
+        branches.sort();
+        for id in branches {
+            let neighbours = &self.adjacency[id];
+            clique.push(id);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbours).copied().collect(),
+                excluded.intersection(neighbours).copied().collect(),
+                best,
+            );
+            clique.pop();
+            candidates.remove(&id);
+            excluded.insert(id);
+        }
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.ids.insert(String::from(name), id);
+        self.names.push(String::from(name));
+        self.adjacency.push(FastSet::default());
+        id
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegreeStats {
+    nodes: usize,
+    total: usize,
+    // Degree to number of nodes with it
+    histogram: BTreeMap<usize, usize>,
+}
+
+impl DegreeStats {
+    pub fn min(&self) -> usize {
+        *self.histogram.keys().next().expect("Stats are never empty")
+    }
+
+    pub fn max(&self) -> usize {
+        *self.histogram.keys().last().expect("Stats are never empty")
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.total as f64 / self.nodes as f64
+    }
+
+    pub fn histogram(&self) -> &BTreeMap<usize, usize> {
+        &self.histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_ordered(graph: &DiGraph<i32>, order: &[i32]) -> bool {
+        order.iter().enumerate().all(|(i, node)| {
+            graph
+                .successors(node)
+                .all(|target| order[..i].iter().all(|before| before != target))
+        })
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph: DiGraph<i32> = [(5, 11), (7, 11), (7, 8), (3, 8), (3, 10), (11, 2), (11, 9)]
+            .into_iter()
+            .collect();
+        let kahn = graph.topological_sort().unwrap();
+        let dfs = graph.topological_sort_dfs().unwrap();
+        assert_eq!(kahn.len(), 8);
+        assert_eq!(dfs.len(), 8);
+        assert!(is_ordered(&graph, &kahn));
+        assert!(is_ordered(&graph, &dfs));
+        assert!(graph.is_acyclic());
+        assert_eq!(graph.find_cycle(), None);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let graph: DiGraph<char> = [('a', 'b'), ('b', 'c'), ('c', 'd'), ('d', 'b'), ('d', 'e')]
+            .into_iter()
+            .collect();
+        assert_eq!(graph.topological_sort(), None);
+        assert_eq!(graph.topological_sort_dfs(), None);
+
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle, vec!['b', 'c', 'd']);
+        for (i, node) in cycle.iter().enumerate() {
+            assert!(graph.contains_edge(node, &cycle[(i + 1) % cycle.len()]));
+        }
+
+        let mut self_loop: DiGraph<char> = DiGraph::new();
+        self_loop.add_edge(&'x', &'x');
+        assert_eq!(self_loop.find_cycle(), Some(vec!['x']));
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let graph: DiGraph<i32> = [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4), (6, 5)]
+            .into_iter()
+            .collect();
+        let mut components: Vec<Vec<i32>> = graph
+            .strongly_connected_components()
+            .into_iter()
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        // Sinks first
+        assert_eq!(components[0], vec![4, 5]);
+        components.sort();
+        assert_eq!(components, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+    }
+
+    #[test]
+    fn test_subgraph_and_reachability() {
+        let graph: DiGraph<i32> = [(1, 2), (2, 3), (3, 1), (3, 4), (5, 6)]
+            .into_iter()
+            .collect();
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 5);
+
+        let reachable = graph.reachable_from(&1);
+        assert_eq!(reachable, HashSet::from([1, 2, 3, 4]));
+        assert!(!graph.can_reach(&4, &4));
+        assert!(!graph.can_reach(&1, &5));
+
+        let sub = graph.subgraph(&[1, 2, 4, 9]);
+        assert_eq!(sub.node_count(), 3);
+        assert_eq!(sub.edge_count(), 1);
+        assert!(sub.contains_edge(&1, &2));
+        assert_eq!(sub.topological_sort(), Some(vec![1, 4, 2]));
+    }
+
+    fn lan_party() -> UnGraph {
+        UnGraph::from_edge_list(
+            "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn",
+            '-',
+        )
+    }
+
+    #[test]
+    fn test_triangles() {
+        let graph = lan_party();
+        assert_eq!(graph.node_count(), 16);
+        assert_eq!(graph.edge_count(), 32);
+        let triangles = graph.triangles();
+        assert_eq!(triangles.len(), 12);
+        assert_eq!(triangles[0], ["aq", "cg", "yn"]);
+        let with_t = triangles
+            .iter()
+            .filter(|triangle| triangle.iter().any(|name| name.starts_with('t')))
+            .count();
+        assert_eq!(with_t, 7);
+    }
+
+    #[test]
+    fn test_max_clique() {
+        let graph = lan_party();
+        assert_eq!(graph.max_clique(), vec!["co", "de", "ka", "ta"]);
+        assert_eq!(UnGraph::new().max_clique(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_components_and_degrees() {
+        let mut graph: UnGraph = [("a", "b"), ("b", "c"), ("d", "e")].into_iter().collect();
+        graph.add_node("f");
+        graph.add_edge("f", "f");
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec!["a", "b", "c"], vec!["d", "e"], vec!["f"]]
+        );
+        assert!(graph.contains_edge("b", "a"));
+        assert_eq!(graph.degree("b"), 2);
+
+        let stats = graph.degree_stats().unwrap();
+        assert_eq!(stats.min(), 0);
+        assert_eq!(stats.max(), 2);
+        assert_eq!(stats.mean(), 1.0);
+        assert_eq!(stats.histogram()[&1], 4);
+        assert!(UnGraph::new().degree_stats().is_none());
+    }
+}
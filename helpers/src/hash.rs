@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+// FxHash as used in rustc. Much faster than SipHash for small keys like ints and
+// points, but offers no protection against crafted collisions, so only use it on
+// trusted input
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(
+                chunk.try_into().expect("Chunk is 8 bytes"),
+            ));
+        }
+        let mut rest = chunks.remainder();
+        if rest.len() >= 4 {
+            self.add_to_hash(
+                u32::from_le_bytes(rest[..4].try_into().expect("Slice is 4 bytes")) as u64,
+            );
+            rest = &rest[4..];
+        }
+        for byte in rest {
+            self.add_to_hash(*byte as u64);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.add_to_hash(i as u64);
+        self.add_to_hash((i >> 64) as u64);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+// Build with FastMap::default() rather than new()
+pub type FastMap<K, V> = HashMap<K, V, FxBuildHasher>;
+pub type FastSet<T> = HashSet<T, FxBuildHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point2D;
+    use std::hash::{BuildHasher, Hash};
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        FxBuildHasher::default().hash_one(value)
+    }
+
+    #[test]
+    fn test_hasher() {
+        assert_eq!(hash_of(&42u64), hash_of(&42u64));
+        assert_ne!(hash_of(&1u64), hash_of(&2u64));
+        assert_ne!(hash_of(&"abcdefghij"), hash_of(&"abcdefghik"));
+        assert_ne!(
+            hash_of(&Point2D::new(&(1, 2))),
+            hash_of(&Point2D::new(&(2, 1)))
+        );
+        // Tail bytes after the 8 byte chunks still count
+        let mut first = FxHasher::default();
+        first.write(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        let mut second = FxHasher::default();
+        second.write(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12]);
+        assert_ne!(first.finish(), second.finish());
+    }
+
+    #[test]
+    fn test_collections() {
+        let mut map: FastMap<Point2D, i32> = FastMap::default();
+        for i in 0..1000 {
+            *map.entry(Point2D::new(&(i % 10, i / 10))).or_insert(0) += i;
+        }
+        assert_eq!(map.len(), 1000);
+        assert_eq!(map[&Point2D::new(&(3, 4))], 43);
+
+        let set: FastSet<i128> = (0..100).map(|i| i % 7).collect();
+        assert_eq!(set.len(), 7);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::Grid;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub type Rgb = [u8; 3];
+
+impl<T> Grid<T> {
+    // Binary PPM (P6), every cell becomes a scale x scale block of pixels
+    pub fn write_ppm<W, F>(&self, writer: &mut W, scale: usize, colour: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        self.write_netpbm(writer, "P6", scale, |value| colour(value).to_vec())
+    }
+
+    // Binary PGM (P5), one grey level per cell
+    pub fn write_pgm<W, F>(&self, writer: &mut W, scale: usize, shade: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> u8,
+    {
+        self.write_netpbm(writer, "P5", scale, |value| vec![shade(value)])
+    }
+
+    pub fn save_ppm<P, F>(&self, path: P, scale: usize, colour: F) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(&T) -> Rgb,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_ppm(&mut writer, scale, colour)?;
+        writer.flush()
+    }
+
+    pub fn save_pgm<P, F>(&self, path: P, scale: usize, shade: F) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(&T) -> u8,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_pgm(&mut writer, scale, shade)?;
+        writer.flush()
+    }
+
+    fn write_netpbm<W, F>(
+        &self,
+        writer: &mut W,
+        magic: &str,
+        scale: usize,
+        pixel: F,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Vec<u8>,
+    {
+        if scale == 0 {
+            panic!("Scale must be at least 1");
+        }
+        let width = self.width() as usize * scale;
+        let height = self.height() as usize * scale;
+        write!(writer, "{magic}\n{width} {height}\n255\n")?;
+
+        for row in self.iter() {
+            let mut line: Vec<u8> = vec![];
+            for value in row {
+                let bytes = pixel(value);
+                for _ in 0..scale {
+                    line.extend_from_slice(&bytes);
+                }
+            }
+            for _ in 0..scale {
+                writer.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Writes numbered PPM frames into a directory, e.g. frames/guard_00003.ppm
+pub struct FrameWriter {
+    directory: PathBuf,
+    prefix: String,
+    scale: usize,
+    frame: usize,
+}
+
+impl FrameWriter {
+    pub fn new<P: AsRef<Path>>(directory: P, prefix: &str, scale: usize) -> io::Result<Self> {
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory: directory.as_ref().to_path_buf(),
+            prefix: String::from(prefix),
+            scale,
+            frame: 0,
+        })
+    }
+
+    pub fn write_frame<T, F>(&mut self, grid: &Grid<T>, colour: F) -> io::Result<PathBuf>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let path = self
+            .directory
+            .join(format!("{}_{:05}.ppm", self.prefix, self.frame));
+        grid.save_ppm(&path, self.scale, colour)?;
+        self.frame += 1;
+        Ok(path)
+    }
+
+    pub fn frames_written(&self) -> usize {
+        self.frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colour(c: &char) -> Rgb {
+        match c {
+            '#' => [255, 0, 0],
+            _ => [0, 0, 0],
+        }
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let mut output: Vec<u8> = vec![];
+        let grid: Grid<char> = Grid::new(
+            "#.
+.#",
+        );
+        grid.write_ppm(&mut output, 1, colour).unwrap();
+
+        let mut expected: Vec<u8> = b"P6\n2 2\n255\n".to_vec();
+        expected.extend_from_slice(&[255, 0, 0, 0, 0, 0, 0, 0, 0, 255, 0, 0]);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_write_pgm_scaled() {
+        let mut output: Vec<u8> = vec![];
+        let grid: Grid<char> = Grid::new(
+            "#.
+.#",
+        );
+        grid.write_pgm(&mut output, 2, |c| if *c == '#' { 9 } else { 1 })
+            .unwrap();
+
+        let mut expected: Vec<u8> = b"P5\n4 4\n255\n".to_vec();
+        expected.extend_from_slice(&[9, 9, 1, 1, 9, 9, 1, 1, 1, 1, 9, 9, 1, 1, 9, 9]);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_frame_writer() {
+        let directory = std::env::temp_dir().join(format!("helpers_frames_{}", std::process::id()));
+        let mut frames = FrameWriter::new(&directory, "test", 1).unwrap();
+        let grid: Grid<char> = Grid::new(
+            "#.
+.#",
+        );
+        let first = frames.write_frame(&grid, colour).unwrap();
+        let second = frames.write_frame(&grid, colour).unwrap();
+
+        assert_eq!(first.file_name().unwrap(), "test_00000.ppm");
+        assert_eq!(second.file_name().unwrap(), "test_00001.ppm");
+        assert_eq!(frames.frames_written(), 2);
+        assert_eq!(fs::read(&first).unwrap().len(), 11 + 12);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
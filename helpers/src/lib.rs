@@ -1,77 +1,171 @@
-use std::ops::{Add, Sub};
+use hash::FastMap;
+use math::gcd;
+use std::hash::Hash;
 use std::str::FromStr;
 
+mod automaton;
+mod bits;
+pub mod combinatorics;
+mod counter;
+mod direction;
+mod gif;
+pub mod graph;
+pub mod hash;
+mod image;
+pub mod math;
+pub mod memo;
+mod parser;
+mod pattern;
+mod point;
+pub mod ranges;
+mod region;
+mod render;
+pub mod search;
+mod sparse;
+mod transform;
+mod wrapping;
+
+pub use automaton::{Automaton, Neighbourhood};
+pub use bits::{BitGrid, DirectionalBitGrid};
+pub use counter::Counter;
+pub use direction::{Direction, ParseDirectionError};
+pub use gif::GifRecorder;
+pub use image::{FrameWriter, Rgb};
+pub use parser::{GridParser, ParseGridError};
+pub use pattern::Pattern;
+pub use point::{Coord, Point2, Point2D, Point3, Point3D};
+pub use region::Region;
+pub use render::{Colour, Overlay};
+pub use sparse::SparseGrid;
+pub use transform::{GridView, Rect};
+pub use wrapping::WrappingGrid;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Grid<T> {
     grid: Vec<Vec<T>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Point2D {
-    x: i32,
-    y: i32,
-}
+// Read access shared by the bounded, wrapping and sparse grids
+pub trait GridRead<T> {
+    fn get(&self, point: &Point2D) -> Option<&T>;
 
-impl Add for Point2D {
-    type Output = Self;
+    fn in_bounds(&self, point: &Point2D) -> bool;
 
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+    fn enumerate<'a>(&'a self) -> impl Iterator<Item = (Point2D, &'a T)> + 'a
+    where
+        T: 'a;
+
+    fn neighbours(&self, point: &Point2D) -> impl Iterator<Item = Point2D> + '_;
+
+    fn find(&self, value: &T) -> Option<Point2D>
+    where
+        T: PartialEq,
+    {
+        self.enumerate()
+            .find(|(_, cell)| *cell == value)
+            .map(|(point, _)| point)
     }
-}
 
-impl Sub for Point2D {
-    type Output = Self;
+    fn positions<'a, F>(&'a self, pred: F) -> impl Iterator<Item = Point2D> + 'a
+    where
+        F: Fn(&T) -> bool + 'a,
+        T: 'a,
+    {
+        self.enumerate()
+            .filter(move |(_, value)| pred(value))
+            .map(|(point, _)| point)
+    }
 
-    fn sub(self, other: Self) -> Self {
-        Self {
-            x: self.x - other.x,
-            y: self.y - other.y,
+    fn group_by_value(&self) -> FastMap<T, Vec<Point2D>>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let mut groups: FastMap<T, Vec<Point2D>> = FastMap::default();
+        for (point, value) in self.enumerate() {
+            groups.entry(value.clone()).or_default().push(point);
         }
+        groups
     }
-}
 
-impl Point2D {
-    pub fn new(coords: &(i32, i32)) -> Self {
-        Self {
-            x: coords.0,
-            y: coords.1,
+    // Walks from start (inclusive) by step until it leaves the grid
+    fn ray<'a>(
+        &'a self,
+        start: &Point2D,
+        step: &Point2D,
+    ) -> impl Iterator<Item = Point2D> + use<'a, Self, T> {
+        if step.x == 0 && step.y == 0 {
+            panic!("Ray step can't be zero");
         }
+        let step = *step;
+        std::iter::successors(Some(*start), move |point| Some(*point + step))
+            .take_while(|point| self.in_bounds(point))
+    }
+
+    // Every lattice point from start to end (both inclusive) that lies inside the grid
+    fn line<'a>(
+        &'a self,
+        start: &Point2D,
+        end: &Point2D,
+    ) -> impl Iterator<Item = Point2D> + use<'a, Self, T> {
+        let diff = *end - *start;
+        let step = diff.primitive();
+        let steps = gcd(diff.x, diff.y);
+        let start = *start;
+        (0..=steps)
+            .map(move |i| start + step * i)
+            .filter(|point| self.in_bounds(point))
     }
 }
 
-impl<T> Grid<T>
-where
-    T: FromStr + Default,
-    <T as FromStr>::Err: std::fmt::Debug,
-{
-    pub fn iter(&self) -> impl Iterator<Item = &Vec<T>> {
-        self.grid.iter()
+impl<T> GridRead<T> for Grid<T> {
+    fn get(&self, point: &Point2D) -> Option<&T> {
+        Grid::get(self, point)
     }
 
-    pub fn new(input: &String) -> Self {
-        let grid: Vec<Vec<T>> = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_string().parse().expect("Failed to parse"))
-                    .collect()
-            })
-            .collect();
+    fn in_bounds(&self, point: &Point2D) -> bool {
+        Grid::in_bounds(self, point)
+    }
 
-        Self { grid }
+    fn enumerate<'a>(&'a self) -> impl Iterator<Item = (Point2D, &'a T)> + 'a
+    where
+        T: 'a,
+    {
+        Grid::enumerate(self)
     }
 
-    fn height(&self) -> i32 {
+    fn neighbours(&self, point: &Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        Grid::neighbours(self, point)
+    }
+}
+
+impl<T: FromStr> Grid<T> {
+    pub fn new(input: &str) -> Self {
+        GridParser::new()
+            .parse_str(input)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        if rows.iter().any(|row| row.len() != rows[0].len()) {
+            panic!("Rows are not all the same length");
+        }
+        Self { grid: rows }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Vec<T>> {
+        self.grid.iter()
+    }
+
+    pub fn height(&self) -> i32 {
         if self.grid.is_empty() {
             panic!("Map is empty");
         }
         self.grid.len() as i32
     }
 
-    fn width(&self) -> i32 {
+    pub fn width(&self) -> i32 {
         if self.grid.is_empty() {
             panic!("Map is empty");
         }
@@ -92,16 +186,161 @@ where
     }
 
     pub fn access_grid(&self, coords: &Point2D) -> &T {
-        let original_coords = self.convert_coords_grid(&coords);
+        let original_coords = self.convert_coords_grid(coords);
         &self.grid[original_coords.0 as usize][original_coords.1 as usize]
     }
 
+    pub fn access_grid_mut(&mut self, coords: &Point2D) -> &mut T {
+        let original_coords = self.convert_coords_grid(coords);
+        &mut self.grid[original_coords.0 as usize][original_coords.1 as usize]
+    }
+
     pub fn in_bounds(&self, point: &Point2D) -> bool {
         point.x >= 0 && point.x < self.width() && point.y >= 0 && point.y < self.height()
     }
 
+    pub fn get(&self, point: &Point2D) -> Option<&T> {
+        if self.in_bounds(point) {
+            Some(self.access_grid(point))
+        } else {
+            None
+        }
+    }
+
     // Hashable point
     pub fn point_to_int(&self, point: &Point2D) -> i32 {
         point.y * self.height() + point.x
     }
+
+    // Up, right, down and left neighbours that are inside the grid
+    pub fn neighbours(&self, point: &Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        let point = *point;
+        Direction::CARDINALS
+            .iter()
+            .map(move |direction| point + direction.offset())
+            .filter(|neighbour| self.in_bounds(neighbour))
+    }
+
+    pub fn ray(&self, start: &Point2D, step: &Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        GridRead::ray(self, start, step)
+    }
+
+    pub fn line(&self, start: &Point2D, end: &Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        GridRead::line(self, start, end)
+    }
+
+    // Every cell alongside its readable coordinates, top row first
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point2D, &T)> + '_ {
+        self.grid.iter().enumerate().flat_map(move |(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(j, value)| (self.convert_coords_readable(&(i as i32, j as i32)), value))
+        })
+    }
+
+    pub fn positions<'a, F>(&'a self, pred: F) -> impl Iterator<Item = Point2D> + 'a
+    where
+        F: Fn(&T) -> bool + 'a,
+    {
+        GridRead::positions(self, pred)
+    }
+
+    // First matching cell, top row first
+    pub fn find(&self, value: &T) -> Option<Point2D>
+    where
+        T: PartialEq,
+    {
+        GridRead::find(self, value)
+    }
+
+    pub fn group_by_value(&self) -> FastMap<T, Vec<Point2D>>
+    where
+        T: Eq + Hash + Clone,
+    {
+        GridRead::group_by_value(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ray() {
+        let grid: Grid<char> = Grid::new(
+            "....
+....
+....",
+        );
+        let result: Vec<Point2D> = grid
+            .ray(&Point2D::new(&(0, 0)), &Point2D::new(&(1, 1)))
+            .collect();
+        let expected = vec![
+            Point2D::new(&(0, 0)),
+            Point2D::new(&(1, 1)),
+            Point2D::new(&(2, 2)),
+        ];
+        assert_eq!(result, expected);
+
+        let outside = grid.ray(&Point2D::new(&(-1, 0)), &Point2D::new(&(1, 0)));
+        assert_eq!(outside.count(), 0);
+    }
+
+    #[test]
+    fn test_line() {
+        let grid: Grid<char> = Grid::new(
+            "....
+....
+....",
+        );
+        let result: Vec<Point2D> = grid
+            .line(&Point2D::new(&(3, 0)), &Point2D::new(&(-1, 2)))
+            .collect();
+        let expected = vec![Point2D::new(&(3, 0)), Point2D::new(&(1, 1))];
+        assert_eq!(result, expected);
+
+        let single: Vec<Point2D> = grid
+            .line(&Point2D::new(&(2, 1)), &Point2D::new(&(2, 1)))
+            .collect();
+        assert_eq!(single, vec![Point2D::new(&(2, 1))]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid: Grid<char> = Grid::new(
+            "....
+....
+....",
+        );
+        let corner: Vec<Point2D> = grid.neighbours(&Point2D::new(&(0, 0))).collect();
+        assert_eq!(corner, vec![Point2D::new(&(0, 1)), Point2D::new(&(1, 0))]);
+        assert_eq!(grid.neighbours(&Point2D::new(&(1, 1))).count(), 4);
+    }
+
+    #[test]
+    fn test_locate() {
+        let grid: Grid<char> = Grid::new(
+            "a.b
+.a.",
+        );
+        assert_eq!(grid.find(&'b'), Some(Point2D::new(&(2, 1))));
+        assert_eq!(grid.find(&'z'), None);
+
+        let a_positions: Vec<Point2D> = grid.positions(|c| *c == 'a').collect();
+        assert_eq!(
+            a_positions,
+            vec![Point2D::new(&(0, 1)), Point2D::new(&(1, 0))]
+        );
+
+        let enumerated: Vec<(Point2D, &char)> = grid.enumerate().take(2).collect();
+        assert_eq!(
+            enumerated,
+            vec![(Point2D::new(&(0, 1)), &'a'), (Point2D::new(&(1, 1)), &'.')]
+        );
+
+        let groups = grid.group_by_value();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&'a'], a_positions);
+        assert_eq!(groups[&'.'].len(), 3);
+    }
 }
@@ -0,0 +1,182 @@
+use crate::Coord;
+
+mod linear;
+mod rational;
+
+pub use linear::{solve_linear, LinearError};
+pub use rational::Rational;
+
+// Always non-negative, gcd(0, 0) is 0
+pub fn gcd<N: Coord>(a: N, b: N) -> N {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != N::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Always non-negative, 0 if either is 0
+pub fn lcm<N: Coord>(a: N, b: N) -> N {
+    if a == N::ZERO || b == N::ZERO {
+        return N::ZERO;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+// Returns (g, x, y) with a * x + b * y == g == gcd(a, b)
+pub fn extended_gcd<N: Coord>(a: N, b: N) -> (N, N, N) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (N::ONE, N::ZERO);
+    let (mut old_y, mut y) = (N::ZERO, N::ONE);
+    while r != N::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < N::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// Like rem_euclid, the result is in 0..modulus
+pub fn modulo<N: Coord>(a: N, modulus: N) -> N {
+    if modulus <= N::ZERO {
+        panic!("Modulus must be positive");
+    }
+    ((a % modulus) + modulus) % modulus
+}
+
+// None unless a and modulus are coprime
+pub fn mod_inverse<N: Coord>(a: N, modulus: N) -> Option<N> {
+    let (g, x, _) = extended_gcd(modulo(a, modulus), modulus);
+    if g == N::ONE {
+        Some(modulo(x, modulus))
+    } else {
+        None
+    }
+}
+
+// Solves x = residue (mod modulus) for every pair. Moduli don't need to be coprime.
+// Returns the smallest non-negative x and the lcm of the moduli, or None if the
+// congruences conflict
+pub fn crt<N: Coord>(congruences: &[(N, N)]) -> Option<(N, N)> {
+    let mut result = (N::ZERO, N::ONE);
+    for (residue, modulus) in congruences {
+        let (value, combined) = result;
+        let residue = modulo(*residue, *modulus);
+        let (g, p, _) = extended_gcd(combined, *modulus);
+        let diff = residue - value;
+        if diff % g != N::ZERO {
+            return None;
+        }
+        let step = *modulus / g;
+        let multiple = modulo(diff / g % step * p, step);
+        let next = combined * step;
+        result = (modulo(value + multiple * combined, next), next);
+    }
+    Some(result)
+}
+
+// Decimal digits in the magnitude, 0 has one digit
+pub fn num_digits<N: Coord>(n: N) -> u32 {
+    let mut n = n.abs();
+    let mut digits = 1;
+    while n >= N::TEN {
+        n = n / N::TEN;
+        digits += 1;
+    }
+    digits
+}
+
+pub fn pow10<N: Coord>(exponent: u32) -> N {
+    (0..exponent).fold(N::ONE, |acc, _| acc * N::TEN)
+}
+
+// Splits off the last count digits, so split_digits(123456, 2) is (1234, 56)
+pub fn split_digits<N: Coord>(n: N, count: u32) -> (N, N) {
+    let divisor = pow10(count);
+    (n / divisor, n % divisor)
+}
+
+// concat_digits(12, 345) is 12345
+pub fn concat_digits<N: Coord>(first: N, second: N) -> N {
+    if second < N::ZERO {
+        panic!("Can't concatenate a negative number");
+    }
+    first * pow10(num_digits(second)) + second
+}
+
+// Undoes concat_digits, None if n doesn't end with the digits of suffix
+pub fn strip_digit_suffix<N: Coord>(n: N, suffix: N) -> Option<N> {
+    if n < N::ZERO || suffix < N::ZERO {
+        return None;
+    }
+    let (rest, last) = split_digits(n, num_digits(suffix));
+    if last == suffix {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-7, 3), (0, 5), (17, 0), (12, -18)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(modulo(-7, 3), 2);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+        // Bus schedule style offsets
+        let buses: [(i64, i64); 4] = [(0, 17), (-2, 13), (-3, 19), (0, 1)];
+        assert_eq!(crt(&buses), Some((3417, 4199)));
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(num_digits(0), 1);
+        assert_eq!(num_digits(9), 1);
+        assert_eq!(num_digits(10), 2);
+        assert_eq!(num_digits(-12345), 5);
+        assert_eq!(num_digits(i64::MAX), 19);
+        assert_eq!(pow10::<i64>(3), 1000);
+        assert_eq!(split_digits(123456, 2), (1234, 56));
+        assert_eq!(split_digits(7, 1), (0, 7));
+        assert_eq!(concat_digits(12, 345), 12345);
+        assert_eq!(concat_digits(12, 0), 120);
+        assert_eq!(strip_digit_suffix(12345, 345), Some(12));
+        assert_eq!(strip_digit_suffix(120, 0), Some(12));
+        assert_eq!(strip_digit_suffix(5, 5), Some(0));
+        assert_eq!(strip_digit_suffix(12345, 45), Some(123));
+        assert_eq!(strip_digit_suffix(12345, 44), None);
+        assert_eq!(strip_digit_suffix(45, 345), None);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::math::Rational;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinearError {
+    // Consistent but with infinitely many solutions
+    Singular,
+    // No solution at all
+    Inconsistent,
+    // Row lengths don't match the number of unknowns or constants
+    Shape,
+}
+
+impl fmt::Display for LinearError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinearError::Singular => write!(f, "System has infinitely many solutions"),
+            LinearError::Inconsistent => write!(f, "System has no solution"),
+            LinearError::Shape => write!(f, "Coefficients and constants don't line up"),
+        }
+    }
+}
+
+impl std::error::Error for LinearError {}
+
+// Gauss-Jordan elimination over exact fractions. Each row of coefficients is one
+// equation, so there can be more equations than unknowns as long as they agree
+pub fn solve_linear(
+    coefficients: &[Vec<Rational>],
+    constants: &[Rational],
+) -> Result<Vec<Rational>, LinearError> {
+    let unknowns = coefficients.first().map_or(0, |row| row.len());
+    if coefficients.len() != constants.len() || coefficients.iter().any(|row| row.len() != unknowns)
+    {
+        return Err(LinearError::Shape);
+    }
+
+    // Augmented matrix, the constant sits in the last column
+    let mut rows: Vec<Vec<Rational>> = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(row, constant)| {
+            let mut row = row.clone();
+            row.push(*constant);
+            row
+        })
+        .collect();
+
+    let mut pivot_row = 0;
+    let mut singular = false;
+    for column in 0..unknowns {
+        let Some(found) = (pivot_row..rows.len()).find(|i| !rows[*i][column].is_zero()) else {
+            singular = true;
+            continue;
+        };
+        rows.swap(pivot_row, found);
+        let pivot = rows[pivot_row][column];
+        for value in rows[pivot_row].iter_mut() {
+            *value /= pivot;
+        }
+        let pivot_values = rows[pivot_row].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if i == pivot_row || factor.is_zero() {
+                continue;
+            }
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_values).skip(column) {
+                *value -= *pivot_value * factor;
+            }
+        }
+        pivot_row += 1;
+    }
+
+    // Any leftover row reads 0 = constant
+    if rows[pivot_row..].iter().any(|row| !row[unknowns].is_zero()) {
+        return Err(LinearError::Inconsistent);
+    }
+    if singular {
+        return Err(LinearError::Singular);
+    }
+    Ok(rows
+        .into_iter()
+        .take(unknowns)
+        .map(|row| row[unknowns])
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_rows(rows: &[&[i128]]) -> Vec<Vec<Rational>> {
+        rows.iter()
+            .map(|row| row.iter().map(|value| Rational::from(*value)).collect())
+            .collect()
+    }
+
+    fn to_column(values: &[i128]) -> Vec<Rational> {
+        values.iter().map(|value| Rational::from(*value)).collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        // Claw machine: 94a + 22b = 8400, 34a + 67b = 5400
+        let result = solve_linear(&to_rows(&[&[94, 22], &[34, 67]]), &to_column(&[8400, 5400]));
+        assert_eq!(result, Ok(to_column(&[80, 40])));
+
+        // Needs a row swap and has a fractional answer
+        let result = solve_linear(
+            &to_rows(&[&[0, 2, 1], &[1, 1, 0], &[2, 0, 3]]),
+            &to_column(&[3, 1, 2]),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            vec![
+                Rational::new(-1, 8),
+                Rational::new(9, 8),
+                Rational::new(3, 4)
+            ]
+        );
+
+        // Extra equations are fine when they agree
+        let result = solve_linear(
+            &to_rows(&[&[1, 1], &[1, -1], &[2, 2]]),
+            &to_column(&[3, 1, 6]),
+        );
+        assert_eq!(result, Ok(to_column(&[2, 1])));
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        let singular = solve_linear(&to_rows(&[&[1, 2], &[2, 4]]), &to_column(&[3, 6]));
+        assert_eq!(singular, Err(LinearError::Singular));
+
+        let inconsistent = solve_linear(&to_rows(&[&[1, 2], &[2, 4]]), &to_column(&[3, 7]));
+        assert_eq!(inconsistent, Err(LinearError::Inconsistent));
+
+        let shape = solve_linear(&to_rows(&[&[1, 2], &[2]]), &to_column(&[3, 7]));
+        assert_eq!(shape, Err(LinearError::Shape));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::math::gcd;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// Exact fraction, always stored in lowest terms with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    pub fn new(numer: i128, denom: i128) -> Self {
+        if denom == 0 {
+            panic!("Rational can't have a zero denominator");
+        }
+        let divisor = gcd(numer, denom) * denom.signum();
+        Self {
+            numer: numer / divisor,
+            denom: denom / divisor,
+        }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    // None unless the value is a whole number
+    pub fn to_integer(&self) -> Option<i128> {
+        if self.is_integer() {
+            Some(self.numer)
+        } else {
+            None
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: self.numer.abs(),
+            denom: self.denom,
+        }
+    }
+
+    pub fn recip(&self) -> Self {
+        Rational::new(self.denom, self.numer)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self {
+            numer: value,
+            denom: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // Dividing by the shared factor first keeps the intermediates small
+        let divisor = gcd(self.denom, other.denom);
+        Rational::new(
+            self.numer * (other.denom / divisor) + other.numer * (self.denom / divisor),
+            self.denom / divisor * other.denom,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let first = gcd(self.numer, other.denom).max(1);
+        let second = gcd(other.numer, self.denom).max(1);
+        Rational::new(
+            (self.numer / first) * (other.numer / second),
+            (self.denom / second) * (other.denom / first),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.is_zero() {
+            panic!("Division by a zero Rational");
+        }
+        self.mul(other.recip())
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl AddAssign for Rational {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Rational {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Rational {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for Rational {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        let half = Rational::new(3, 6);
+        assert_eq!(half, Rational::new(-1, -2));
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Rational::new(4, -8).to_string(), "-1/2");
+        assert_eq!(Rational::new(0, -5), Rational::ZERO);
+        assert_eq!(Rational::new(10, 5).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let third = Rational::new(1, 3);
+        let sixth = Rational::new(1, 6);
+        assert_eq!(third + sixth, Rational::new(1, 2));
+        assert_eq!(third - sixth, sixth);
+        assert_eq!(third * sixth, Rational::new(1, 18));
+        assert_eq!(third / sixth, Rational::from(2));
+        assert_eq!(-third, Rational::new(-1, 3));
+        assert_eq!(Rational::new(-2, 3).recip(), Rational::new(-3, 2));
+
+        let mut total = Rational::ZERO;
+        for n in 1..=4 {
+            total += Rational::new(1, n * (n + 1));
+        }
+        assert_eq!(total, Rational::new(4, 5));
+        assert!(Rational::new(-1, 2) < third);
+        assert!(Rational::new(2, 3) > Rational::new(3, 5));
+    }
+}
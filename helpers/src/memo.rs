@@ -0,0 +1,114 @@
+use crate::hash::FastMap;
+use std::hash::Hash;
+
+// Cache for recursive functions. The compute closure gets the memo back so it
+// can recurse through it, e.g.
+//     fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//         memo.get_or_compute(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+//     }
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: FastMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: FastMap::default(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    // Looks without touching the statistics
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    // Number of cached results
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    // Every miss runs the compute closure once
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    // Fraction of lookups answered from the cache, 0 before any lookups
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+
+    // Drops the cached results and resets the statistics
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo| fib(n - 1, memo) + fib(n - 2, memo))
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(memo.misses(), 89);
+        assert_eq!(memo.hits(), 87);
+        assert_eq!(memo.peek(&10), Some(&55));
+
+        fib(90, &mut memo);
+        assert_eq!(memo.hits(), 88);
+        assert!(memo.hit_rate() > 0.49);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.hit_rate(), 0.0);
+    }
+}
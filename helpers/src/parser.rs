@@ -0,0 +1,185 @@
+use crate::Grid;
+use std::fmt;
+use std::io::Read;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum ParseGridError {
+    Empty,
+    // Line numbers start at 1
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: String,
+    },
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "Grid input is empty"),
+            ParseGridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Line {line} has {found} cells but the first row has {expected}"
+            ),
+            ParseGridError::InvalidCell { line, column, cell } => {
+                write!(f, "Invalid cell '{cell}' on line {line} column {column}")
+            }
+            ParseGridError::Io(err) => write!(f, "Failed to read grid input: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CellSplit {
+    Chars,
+    Whitespace,
+}
+
+// Checked grid loading. Rows must all be the same width, \r is stripped and
+// trailing blank lines are ignored
+#[derive(Debug, Clone, Copy)]
+pub struct GridParser {
+    split: CellSplit,
+}
+
+impl Default for GridParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GridParser {
+    // One cell per character
+    pub fn new() -> Self {
+        Self {
+            split: CellSplit::Chars,
+        }
+    }
+
+    // Cells separated by whitespace, e.g. numeric matrices
+    pub fn whitespace_separated(mut self) -> Self {
+        self.split = CellSplit::Whitespace;
+        self
+    }
+
+    pub fn parse_str<T: FromStr>(&self, input: &str) -> Result<Grid<T>, ParseGridError> {
+        let mut lines: Vec<&str> = input
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        if lines.is_empty() {
+            return Err(ParseGridError::Empty);
+        }
+
+        let mut rows: Vec<Vec<T>> = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let cells: Vec<&str> = match self.split {
+                CellSplit::Chars => line
+                    .char_indices()
+                    .map(|(j, c)| &line[j..j + c.len_utf8()])
+                    .collect(),
+                CellSplit::Whitespace => line.split_whitespace().collect(),
+            };
+            if let Some(first) = rows.first() {
+                if cells.len() != first.len() {
+                    return Err(ParseGridError::Ragged {
+                        line: i + 1,
+                        expected: first.len(),
+                        found: cells.len(),
+                    });
+                }
+            }
+            let mut row: Vec<T> = Vec::with_capacity(cells.len());
+            for (j, cell) in cells.into_iter().enumerate() {
+                match cell.parse() {
+                    Ok(value) => row.push(value),
+                    Err(_) => {
+                        return Err(ParseGridError::InvalidCell {
+                            line: i + 1,
+                            column: j + 1,
+                            cell: String::from(cell),
+                        })
+                    }
+                }
+            }
+            rows.push(row);
+        }
+        if rows[0].is_empty() {
+            return Err(ParseGridError::Empty);
+        }
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn parse_reader<T: FromStr, R: Read>(
+        &self,
+        mut reader: R,
+    ) -> Result<Grid<T>, ParseGridError> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(ParseGridError::Io)?;
+        self.parse_str(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chars() {
+        let grid: Grid<char> = GridParser::new().parse_str("ab\r\ncd\r\n\n").unwrap();
+        assert_eq!(grid.to_string(), "ab\ncd");
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        let grid: Grid<i64> = GridParser::new()
+            .whitespace_separated()
+            .parse_reader("10 -2  3\n 4 5 600\n".as_bytes())
+            .unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.iter().next().unwrap(), &vec![10, -2, 3]);
+        assert_eq!(grid.iter().last().unwrap(), &vec![4, 5, 600]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let ragged = GridParser::new().parse_str::<char>("abc\nabc\nab");
+        assert!(matches!(
+            ragged,
+            Err(ParseGridError::Ragged {
+                line: 3,
+                expected: 3,
+                found: 2
+            })
+        ));
+
+        let invalid = GridParser::new()
+            .whitespace_separated()
+            .parse_str::<i64>("1 2\n3 x");
+        assert_eq!(
+            invalid.unwrap_err().to_string(),
+            "Invalid cell 'x' on line 2 column 2"
+        );
+
+        let empty = GridParser::new().parse_str::<char>("\n\n");
+        assert!(matches!(empty, Err(ParseGridError::Empty)));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::hash::FastSet;
+use crate::{Grid, Point2D};
+
+// Small template where None cells match anything
+pub struct Pattern<T> {
+    variants: Vec<Grid<Option<T>>>,
+}
+
+impl<T> Pattern<T>
+where
+    T: Clone + PartialEq,
+{
+    pub fn new(template: Grid<Option<T>>) -> Self {
+        Self {
+            variants: vec![template],
+        }
+    }
+
+    // Adds the quarter turns of every variant so far
+    pub fn with_rotations(mut self) -> Self {
+        for variant in self.variants.clone() {
+            let mut rotated = variant;
+            for _ in 0..3 {
+                rotated = rotated.rotate_cw();
+                self.add_variant(rotated.clone());
+            }
+        }
+        self
+    }
+
+    // Adds the mirror image of every variant so far
+    pub fn with_reflections(mut self) -> Self {
+        for variant in self.variants.clone() {
+            self.add_variant(variant.flip_h());
+        }
+        self
+    }
+
+    pub fn variants(&self) -> &Vec<Grid<Option<T>>> {
+        &self.variants
+    }
+
+    // Symmetric templates would otherwise match the same place twice
+    fn add_variant(&mut self, variant: Grid<Option<T>>) {
+        if !self.variants.contains(&variant) {
+            self.variants.push(variant);
+        }
+    }
+}
+
+impl Pattern<char> {
+    pub fn from_str_with_wildcard(input: &str, wildcard: char) -> Self {
+        let grid: Grid<char> = Grid::new(input);
+        Self::new(Grid::from_rows(
+            grid.iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|c| if *c == wildcard { None } else { Some(*c) })
+                        .collect()
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    // Bottom left anchor of every placement where a variant matches. Different
+    // variants with wildcards can match the same cells, e.g. both diagonals of
+    // "a?/?a" on a block of a's, so each anchor and footprint is reported once
+    pub fn match_pattern(&self, pattern: &Pattern<T>) -> Vec<Point2D> {
+        let mut anchors: Vec<Point2D> = vec![];
+        let mut placements: FastSet<(Point2D, i32, i32)> = FastSet::default();
+        for variant in &pattern.variants {
+            let cells: Vec<(Point2D, &T)> = variant
+                .enumerate()
+                .filter_map(|(offset, cell)| cell.as_ref().map(|value| (offset, value)))
+                .collect();
+            for y in 0..=(self.height() - variant.height()) {
+                for x in 0..=(self.width() - variant.width()) {
+                    let anchor = Point2D::new(&(x, y));
+                    if cells
+                        .iter()
+                        .all(|(offset, value)| self.access_grid(&(anchor + *offset)) == *value)
+                        && placements.insert((anchor, variant.width(), variant.height()))
+                    {
+                        anchors.push(anchor);
+                    }
+                }
+            }
+        }
+        anchors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_pattern() {
+        let grid: Grid<char> = Grid::new(
+            "ab.a
+.bab
+a.ab",
+        );
+        let pattern = Pattern::from_str_with_wildcard("a?\n?b", '?');
+        let mut anchors = grid.match_pattern(&pattern);
+        anchors.sort_by_key(|point| (point.x, point.y));
+        assert_eq!(anchors, vec![Point2D::new(&(0, 1)), Point2D::new(&(2, 0))]);
+    }
+
+    #[test]
+    fn test_variants() {
+        let pattern = Pattern::from_str_with_wildcard("ab", '?').with_rotations();
+        assert_eq!(pattern.variants().len(), 4);
+
+        let symmetric = Pattern::from_str_with_wildcard("a?a\n?b?\na?a", '?')
+            .with_rotations()
+            .with_reflections();
+        assert_eq!(symmetric.variants().len(), 1);
+
+        let l_shape = Pattern::from_str_with_wildcard("a?\naa", '?')
+            .with_rotations()
+            .with_reflections();
+        assert_eq!(l_shape.variants().len(), 4);
+    }
+
+    #[test]
+    fn test_symmetric_matches() {
+        let grid: Grid<char> = Grid::new(
+            "aaa
+aaa",
+        );
+        let diagonal = Pattern::from_str_with_wildcard("a?\n?a", '?')
+            .with_rotations()
+            .with_reflections();
+        assert_eq!(diagonal.variants().len(), 2);
+        assert_eq!(grid.match_pattern(&diagonal).len(), 2);
+
+        // A turned footprint is a different placement even at the same anchor
+        let pair = Pattern::from_str_with_wildcard("aa", '?').with_rotations();
+        assert_eq!(grid.match_pattern(&pair).len(), 7);
+    }
+
+    #[test]
+    fn test_pattern_larger_than_grid() {
+        let grid: Grid<char> = Grid::new("ab");
+        let pattern = Pattern::from_str_with_wildcard("abc", '?');
+        assert!(grid.match_pattern(&pattern).is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::math::gcd;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
+
+// Signed integer types that points can be built from
+pub trait Coord:
+    Copy
+    + Debug
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TEN: Self;
+
+    fn abs(self) -> Self {
+        if self < Self::ZERO {
+            -self
+        } else {
+            self
+        }
+    }
+
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            std::cmp::Ordering::Less => -Self::ONE,
+            std::cmp::Ordering::Equal => Self::ZERO,
+            std::cmp::Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const TEN: Self = 10;
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point2<N> {
+    pub(crate) x: N,
+    pub(crate) y: N,
+}
+
+pub type Point2D = Point2<i32>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point3<N> {
+    x: N,
+    y: N,
+    z: N,
+}
+
+pub type Point3D = Point3<i32>;
+
+// Component-wise operators shared by both point types
+macro_rules! impl_point_ops {
+    ($point:ident, $($field:ident),+) => {
+        impl<N: Coord> Add for $point<N> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self {
+                    $($field: self.$field + other.$field),+
+                }
+            }
+        }
+
+        impl<N: Coord> Sub for $point<N> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self {
+                    $($field: self.$field - other.$field),+
+                }
+            }
+        }
+
+        impl<N: Coord> AddAssign for $point<N> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<N: Coord> SubAssign for $point<N> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<N: Coord> Neg for $point<N> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self {
+                    $($field: -self.$field),+
+                }
+            }
+        }
+
+        impl<N: Coord> Mul<N> for $point<N> {
+            type Output = Self;
+
+            fn mul(self, scalar: N) -> Self {
+                Self {
+                    $($field: self.$field * scalar),+
+                }
+            }
+        }
+
+        // Integer division, rounds towards zero
+        impl<N: Coord> Div<N> for $point<N> {
+            type Output = Self;
+
+            fn div(self, scalar: N) -> Self {
+                Self {
+                    $($field: self.$field / scalar),+
+                }
+            }
+        }
+
+        impl<N: Coord> $point<N> {
+            $(
+                pub fn $field(&self) -> N {
+                    self.$field
+                }
+            )+
+
+            pub fn manhattan(&self, other: &Self) -> N {
+                let diff = *self - *other;
+                N::ZERO $(+ diff.$field.abs())+
+            }
+
+            pub fn chebyshev(&self, other: &Self) -> N {
+                let diff = *self - *other;
+                let mut result = N::ZERO;
+                $(result = result.max(diff.$field.abs());)+
+                result
+            }
+
+            pub fn signum(&self) -> Self {
+                Self {
+                    $($field: self.$field.signum()),+
+                }
+            }
+
+            // Smallest lattice step in the same direction, the origin stays as it is
+            pub fn primitive(&self) -> Self {
+                let mut divisor = N::ZERO;
+                $(divisor = gcd(divisor, self.$field);)+
+                if divisor == N::ZERO {
+                    *self
+                } else {
+                    *self / divisor
+                }
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2, x, y);
+impl_point_ops!(Point3, x, y, z);
+
+impl<N: Coord> From<(N, N)> for Point2<N> {
+    fn from(coords: (N, N)) -> Self {
+        Self::new(&coords)
+    }
+}
+
+impl<N: Coord> From<(N, N, N)> for Point3<N> {
+    fn from(coords: (N, N, N)) -> Self {
+        Self::new(&coords)
+    }
+}
+
+impl<N: Coord> Point2<N> {
+    pub fn new(coords: &(N, N)) -> Self {
+        Self {
+            x: coords.0,
+            y: coords.1,
+        }
+    }
+
+    // Quarter turns about the origin, with y pointing up as in readable coordinates
+    pub fn rotate_cw(&self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+}
+
+impl<N: Coord> Point3<N> {
+    pub fn new(coords: &(N, N, N)) -> Self {
+        Self {
+            x: coords.0,
+            y: coords.1,
+            z: coords.2,
+        }
+    }
+
+    // Squared so it stays an integer
+    pub fn euclidean_squared(&self, other: &Self) -> N {
+        let diff = *self - *other;
+        diff.x * diff.x + diff.y * diff.y + diff.z * diff.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let a = Point2D::new(&(3, -4));
+        let b: Point2D = (1, 2).into();
+        assert_eq!(a + b, Point2D::new(&(4, -2)));
+        assert_eq!(a - b, Point2D::new(&(2, -6)));
+        assert_eq!(-a, Point2D::new(&(-3, 4)));
+        assert_eq!(a * 3, Point2D::new(&(9, -12)));
+        assert_eq!(a / 2, Point2D::new(&(1, -2)));
+
+        let mut c = a;
+        c += b;
+        c -= b * 2;
+        assert_eq!(c, Point2D::new(&(2, -6)));
+        assert_eq!((c.x(), c.y()), (2, -6));
+    }
+
+    #[test]
+    fn test_geometry() {
+        let a = Point2D::new(&(3, -4));
+        let origin = Point2D::new(&(0, 0));
+        assert_eq!(a.manhattan(&origin), 7);
+        assert_eq!(a.chebyshev(&origin), 4);
+        assert_eq!(a.signum(), Point2D::new(&(1, -1)));
+
+        let up = Point2D::new(&(0, 1));
+        assert_eq!(up.rotate_cw(), Point2D::new(&(1, 0)));
+        assert_eq!(up.rotate_ccw(), Point2D::new(&(-1, 0)));
+        assert_eq!(a.rotate_cw().rotate_ccw(), a);
+    }
+
+    #[test]
+    fn test_primitive() {
+        let input = [(6, -9), (0, 4), (-5, 0), (7, 3), (0, 0)];
+        let expected = [(2, -3), (0, 1), (-1, 0), (7, 3), (0, 0)];
+        for (i, coords) in input.iter().enumerate() {
+            assert_eq!(Point2D::new(coords).primitive(), Point2D::new(&expected[i]));
+        }
+    }
+
+    #[test]
+    fn test_wide_points() {
+        let far: Point2<i64> = Point2::new(&(5_000_000_000, -3));
+        let near: Point2<i64> = (1, 1).into();
+        assert_eq!(far.manhattan(&near), 5_000_000_003);
+        assert_eq!((far * 2).x(), 10_000_000_000);
+
+        let huge: Point2<i128> = Point2::new(&(i64::MAX as i128 * 4, i64::MAX as i128 * 6));
+        assert_eq!(huge.primitive(), Point2::new(&(2, 3)));
+    }
+
+    #[test]
+    fn test_point3() {
+        let a: Point3<i64> = Point3::new(&(1, -2, 3));
+        let b: Point3<i64> = (4, 2, -9).into();
+        assert_eq!(a + b, Point3::new(&(5, 0, -6)));
+        assert_eq!(b - a, Point3::new(&(3, 4, -12)));
+        assert_eq!(-a * 2, Point3::new(&(-2, 4, -6)));
+        assert_eq!(a.manhattan(&b), 19);
+        assert_eq!(a.chebyshev(&b), 12);
+        assert_eq!(a.euclidean_squared(&b), 169);
+        assert_eq!((b - a).signum(), Point3::new(&(1, 1, -1)));
+        assert_eq!(
+            Point3D::new(&(4, -8, 12)).primitive(),
+            Point3::new(&(1, -2, 3))
+        );
+        assert_eq!((a.x(), a.y(), a.z()), (1, -2, 3));
+    }
+}
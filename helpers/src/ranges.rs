@@ -0,0 +1,360 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound::{Excluded, Unbounded};
+use std::ops::Range;
+
+// Disjoint half-open integer intervals. Touching or overlapping intervals are
+// merged on insert, so every stored range is separated by a gap
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RangeSet {
+    // Start to end
+    ranges: BTreeMap<i64, i64>,
+    // Length to starts of the gaps between neighbouring ranges, for first_gap
+    gaps_by_length: BTreeMap<i64, BTreeSet<i64>>,
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+            gaps_by_length: BTreeMap::new(),
+        }
+    }
+
+    // Empty ranges are ignored
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        self.reindex_gaps(range.clone(), |set| set.insert_range(range));
+    }
+
+    pub fn remove(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        self.reindex_gaps(range.clone(), |set| set.remove_range(range));
+    }
+
+    fn insert_range(&mut self, range: Range<i64>) {
+        let (mut start, mut end) = (range.start, range.end);
+        // Only the range starting at or before start can reach into it from the left
+        if let Some((before_start, before_end)) = self.ranges.range(..=start).next_back() {
+            if *before_end >= start {
+                start = *before_start;
+                end = end.max(*before_end);
+            }
+        }
+        let swallowed: Vec<(i64, i64)> = self
+            .ranges
+            .range(start..=end)
+            .map(|(s, e)| (*s, *e))
+            .collect();
+        for (s, e) in swallowed {
+            end = end.max(e);
+            self.ranges.remove(&s);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    fn remove_range(&mut self, range: Range<i64>) {
+        let mut affected: Vec<(i64, i64)> = vec![];
+        if let Some((s, e)) = self.ranges.range(..range.start).next_back() {
+            if *e > range.start {
+                affected.push((*s, *e));
+            }
+        }
+        affected.extend(self.ranges.range(range.clone()).map(|(s, e)| (*s, *e)));
+        for (s, e) in affected {
+            self.ranges.remove(&s);
+            if s < range.start {
+                self.ranges.insert(s, range.start);
+            }
+            if e > range.end {
+                self.ranges.insert(range.end, e);
+            }
+        }
+    }
+
+    pub fn union_with(&mut self, other: &RangeSet) {
+        for range in other.iter() {
+            self.insert(range);
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.containing(value).is_some()
+    }
+
+    // The stored range that holds value
+    pub fn containing(&self, value: i64) -> Option<Range<i64>> {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .filter(|(_, end)| **end > value)
+            .map(|(start, end)| *start..*end)
+    }
+
+    // True if any value in range is covered
+    pub fn overlaps(&self, range: &Range<i64>) -> bool {
+        !range.is_empty()
+            && self
+                .ranges
+                .range(..range.end)
+                .next_back()
+                .is_some_and(|(_, end)| *end > range.start)
+    }
+
+    // True if every value in range is covered
+    pub fn covers(&self, range: &Range<i64>) -> bool {
+        range.is_empty()
+            || self
+                .containing(range.start)
+                .is_some_and(|found| found.end >= range.end)
+    }
+
+    // Number of disjoint ranges
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Number of values covered
+    pub fn total_len(&self) -> i64 {
+        self.ranges.iter().map(|(start, end)| end - start).sum()
+    }
+
+    // Lowest first
+    pub fn iter(&self) -> impl Iterator<Item = Range<i64>> + '_ {
+        self.ranges.iter().map(|(start, end)| *start..*end)
+    }
+
+    // Everything inside bounds that isn't covered
+    pub fn complement(&self, bounds: &Range<i64>) -> RangeSet {
+        self.gaps(bounds).collect()
+    }
+
+    // Start of the lowest uncovered run of at least length values inside bounds.
+    // Takes one lookup per distinct gap length that is long enough, rather than
+    // walking every gap
+    pub fn first_gap(&self, length: i64, bounds: &Range<i64>) -> Option<i64> {
+        // The gap holding bounds.start, or the one just after the range covering it
+        let first = self
+            .containing(bounds.start)
+            .map_or(bounds.start, |covering| covering.end);
+        if first >= bounds.end {
+            return None;
+        }
+        let first_end = self
+            .ranges
+            .range((Excluded(first), Unbounded))
+            .next()
+            .map_or(bounds.end, |(start, _)| (*start).min(bounds.end));
+        if first_end - first >= length.max(1) {
+            return Some(first);
+        }
+        let fits = |start: i64, end: i64| end.min(bounds.end) - start >= length;
+        // Later gaps in a bucket start even further right, so only the first can
+        // be the lowest that still fits inside bounds
+        let between = self
+            .gaps_by_length
+            .range(length..)
+            .filter_map(|(gap_length, starts)| {
+                starts
+                    .range((Excluded(first), Unbounded))
+                    .next()
+                    .filter(|start| fits(**start, **start + gap_length))
+                    .copied()
+            })
+            .min();
+        // Open space after the last range
+        let after = self
+            .ranges
+            .last_key_value()
+            .map(|(_, end)| *end)
+            .filter(|end| *end > first && fits(*end, bounds.end));
+        between.into_iter().chain(after).min()
+    }
+
+    // Runs change, which only touches ranges overlapping or next to span, and
+    // updates the gap index for the ranges around it
+    fn reindex_gaps<F>(&mut self, span: Range<i64>, change: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let low = self
+            .ranges
+            .range(..span.start)
+            .next_back()
+            .map_or(i64::MIN, |(start, _)| *start);
+        let high = self
+            .ranges
+            .range((Excluded(span.end), Unbounded))
+            .next()
+            .map_or(i64::MAX, |(start, _)| *start);
+        for gap in self.gaps_between(low, high) {
+            let starts = self
+                .gaps_by_length
+                .get_mut(&(gap.end - gap.start))
+                .expect("Gap is indexed");
+            starts.remove(&gap.start);
+            if starts.is_empty() {
+                self.gaps_by_length.remove(&(gap.end - gap.start));
+            }
+        }
+        change(self);
+        for gap in self.gaps_between(low, high) {
+            self.gaps_by_length
+                .entry(gap.end - gap.start)
+                .or_default()
+                .insert(gap.start);
+        }
+    }
+
+    // Gaps between neighbouring ranges that start from low up to high
+    fn gaps_between(&self, low: i64, high: i64) -> Vec<Range<i64>> {
+        let ranges: Vec<(i64, i64)> = self
+            .ranges
+            .range(low..=high)
+            .map(|(start, end)| (*start, *end))
+            .collect();
+        ranges.windows(2).map(|pair| pair[0].1..pair[1].0).collect()
+    }
+
+    fn gaps<'a>(&'a self, bounds: &Range<i64>) -> impl Iterator<Item = Range<i64>> + 'a {
+        let bounds = bounds.clone();
+        // Ranges that reach into bounds, in order, followed by a marker at the end
+        let mut cursor = bounds.start;
+        self.ranges
+            .range(..bounds.end)
+            .map(|(start, end)| (*start, *end))
+            .filter(move |(_, end)| *end > bounds.start)
+            .chain(std::iter::once((bounds.end, bounds.end)))
+            .filter_map(move |(start, end)| {
+                let gap = cursor..start.min(bounds.end);
+                cursor = cursor.max(end);
+                if gap.is_empty() {
+                    None
+                } else {
+                    Some(gap)
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_merge() {
+        let mut set: RangeSet = [5..8, 0..2, 10..12].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        // Touching ranges merge
+        set.insert(2..3);
+        set.insert(8..10);
+        assert_eq!(set.iter().collect::<Vec<Range<i64>>>(), vec![0..3, 5..12]);
+        // Overlapping several at once
+        set.insert(-1..6);
+        assert_eq!(set.iter().collect::<Vec<Range<i64>>>(), vec![-1..12]);
+        set.insert(3..4);
+        set.insert(4..4);
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.total_len(), 13);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set: RangeSet = [0..10, 20..30].into_iter().collect();
+        set.remove(3..5);
+        set.remove(8..22);
+        assert_eq!(
+            set.iter().collect::<Vec<Range<i64>>>(),
+            vec![0..3, 5..8, 22..30]
+        );
+        set.remove(-5..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: RangeSet = [0..3, 5..8].into_iter().collect();
+        assert!(set.contains(0));
+        assert!(!set.contains(3));
+        assert!(set.contains(7));
+        assert_eq!(set.containing(6), Some(5..8));
+        assert_eq!(set.containing(4), None);
+
+        assert!(set.overlaps(&(2..4)));
+        assert!(set.overlaps(&(4..6)));
+        assert!(!set.overlaps(&(3..5)));
+        assert!(!set.overlaps(&(1..1)));
+        assert!(set.covers(&(5..8)));
+        assert!(!set.covers(&(2..6)));
+
+        let mut other = RangeSet::new();
+        other.insert(3..5);
+        other.union_with(&set);
+        assert_eq!(other.iter().collect::<Vec<Range<i64>>>(), vec![0..8]);
+    }
+
+    #[test]
+    fn test_gaps() {
+        let set: RangeSet = [0..2, 4..5, 8..9].into_iter().collect();
+        assert_eq!(
+            set.complement(&(1..12)).iter().collect::<Vec<Range<i64>>>(),
+            vec![2..4, 5..8, 9..12]
+        );
+        assert_eq!(
+            set.complement(&(4..5)).iter().collect::<Vec<Range<i64>>>(),
+            vec![]
+        );
+        assert_eq!(set.first_gap(1, &(0..12)), Some(2));
+        assert_eq!(set.first_gap(3, &(0..12)), Some(5));
+        assert_eq!(set.first_gap(3, &(0..7)), None);
+        assert_eq!(set.first_gap(4, &(0..20)), Some(9));
+        assert_eq!(RangeSet::new().first_gap(2, &(3..5)), Some(3));
+        assert_eq!(set.first_gap(2, &(3..12)), Some(5));
+        assert_eq!(set.first_gap(3, &(6..12)), Some(9));
+        assert_eq!(set.first_gap(1, &(0..2)), None);
+    }
+
+    #[test]
+    fn test_first_gap_matches_scan() {
+        // Compares the indexed lookup against walking every gap while the set changes
+        let mut set = RangeSet::new();
+        let mut seed: i64 = 7;
+        for _ in 0..500 {
+            seed = (seed * 1_103_515_245 + 12_345) % 2_147_483_648;
+            let start = seed % 200;
+            let length = seed / 200 % 6;
+            if seed % 3 == 0 {
+                set.remove(start..start + length);
+            } else {
+                set.insert(start..start + length);
+            }
+            let bounds = seed % 50..seed % 50 + 150;
+            for wanted in 1..6 {
+                let scanned = set
+                    .gaps(&bounds)
+                    .find(|gap| gap.end - gap.start >= wanted)
+                    .map(|gap| gap.start);
+                assert_eq!(set.first_gap(wanted, &bounds), scanned);
+            }
+        }
+        let rebuilt: RangeSet = set.iter().collect();
+        assert_eq!(rebuilt, set);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::hash::FastSet;
+use crate::{Direction, Grid, Point2D};
+use std::collections::VecDeque;
+
+// Connected set of cells from a flood fill
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    cells: Vec<Point2D>,
+}
+
+impl Region {
+    pub fn cells(&self) -> &Vec<Point2D> {
+        &self.cells
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // Cell edges that don't touch another cell of the region
+    pub fn perimeter(&self) -> usize {
+        let cells: FastSet<Point2D> = self.cells.iter().copied().collect();
+        self.cells
+            .iter()
+            .map(|cell| {
+                Direction::CARDINALS
+                    .iter()
+                    .filter(|direction| !cells.contains(&(*cell + direction.offset())))
+                    .count()
+            })
+            .sum()
+    }
+
+    // Straight sides of the outline, holes included. A polygon has as many sides as corners
+    pub fn sides(&self) -> usize {
+        let cells: FastSet<Point2D> = self.cells.iter().copied().collect();
+        let mut corners = 0;
+        for cell in &self.cells {
+            for (dx, dy) in [(1, 1), (1, -1), (-1, -1), (-1, 1)] {
+                let horizontal = cells.contains(&(*cell + Point2D::new(&(dx, 0))));
+                let vertical = cells.contains(&(*cell + Point2D::new(&(0, dy))));
+                let diagonal = cells.contains(&(*cell + Point2D::new(&(dx, dy))));
+                // Outer corner, or inner corner where the region wraps around an empty cell
+                if (!horizontal && !vertical) || (horizontal && vertical && !diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+}
+
+impl<T> Grid<T> {
+    // same_region is given the current cell then its neighbour
+    pub fn flood_fill<F>(&self, start: &Point2D, same_region: F) -> Region
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut seen: FastSet<Point2D> = FastSet::default();
+        self.fill_from(start, &same_region, &mut |point| seen.insert(*point))
+    }
+
+    // Splits the whole grid into regions, eq should be an equivalence relation
+    pub fn components<F>(&self, eq: F) -> Vec<Region>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut seen: Vec<Vec<bool>> = self.iter().map(|row| vec![false; row.len()]).collect();
+        let mut regions: Vec<Region> = vec![];
+        let points: Vec<Point2D> = self.enumerate().map(|(point, _)| point).collect();
+        for point in points {
+            let (i, j) = self.convert_coords_grid(&point);
+            if seen[i as usize][j as usize] {
+                continue;
+            }
+            regions.push(self.fill_from(&point, &eq, &mut |point| {
+                let (i, j) = self.convert_coords_grid(point);
+                !std::mem::replace(&mut seen[i as usize][j as usize], true)
+            }));
+        }
+        regions
+    }
+
+    // Breadth first fill, mark_seen returns false if the point was already seen
+    fn fill_from<F, S>(&self, start: &Point2D, same_region: &F, mark_seen: &mut S) -> Region
+    where
+        F: Fn(&T, &T) -> bool,
+        S: FnMut(&Point2D) -> bool,
+    {
+        if !self.in_bounds(start) {
+            panic!("Flood fill must start inside the grid");
+        }
+        let mut cells: Vec<Point2D> = vec![];
+        let mut queue: VecDeque<Point2D> = VecDeque::new();
+        mark_seen(start);
+        queue.push_back(*start);
+
+        while let Some(point) = queue.pop_front() {
+            cells.push(point);
+            let value = self.access_grid(&point);
+            for neighbour in self.neighbours(&point) {
+                if same_region(value, self.access_grid(&neighbour)) && mark_seen(&neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        Region { cells }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flood_fill() {
+        let grid: Grid<char> = Grid::new(
+            "AAB
+ABB
+AAA",
+        );
+        let region = grid.flood_fill(&Point2D::new(&(0, 0)), |a, b| a == b);
+        assert_eq!(region.area(), 6);
+        assert_eq!(region.cells()[0], Point2D::new(&(0, 0)));
+
+        let b_region = grid.flood_fill(&Point2D::new(&(2, 2)), |a, b| a == b);
+        assert_eq!(b_region.area(), 3);
+        assert_eq!(b_region.perimeter(), 8);
+        assert_eq!(b_region.sides(), 6);
+    }
+
+    #[test]
+    fn test_components() {
+        let grid: Grid<char> = Grid::new(
+            "AAAA
+BBCD
+BBCC
+EEEC",
+        );
+        let regions = grid.components(|a, b| a == b);
+        let summary: Vec<(usize, usize, usize)> = regions
+            .iter()
+            .map(|region| (region.area(), region.perimeter(), region.sides()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+    }
+
+    #[test]
+    fn test_sides_with_hole() {
+        let grid: Grid<char> = Grid::new(
+            "OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO",
+        );
+        let region = grid.flood_fill(&Point2D::new(&(0, 0)), |a, b| a == b);
+        assert_eq!(region.area(), 21);
+        assert_eq!(region.perimeter(), 36);
+        assert_eq!(region.sides(), 20);
+        assert_eq!(grid.components(|a, b| a == b).len(), 5);
+    }
+}
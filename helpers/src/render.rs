@@ -0,0 +1,248 @@
+use crate::{Grid, Point2D};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    fn ansi_code(&self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Mark {
+    glyph: char,
+    colour: Option<Colour>,
+}
+
+impl fmt::Display for Mark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.colour {
+            Some(colour) => write!(f, "\x1b[{}m{}\x1b[0m", colour.ansi_code(), self.glyph),
+            None => write!(f, "{}", self.glyph),
+        }
+    }
+}
+
+// Draws marks on top of a grid without touching it, later marks win
+pub struct Overlay<'a, T> {
+    grid: &'a Grid<T>,
+    glyph: Box<dyn Fn(&T) -> char + 'a>,
+    marks: Vec<(Point2D, Mark)>,
+}
+
+impl<T: Clone + Into<char>> Grid<T> {
+    pub fn overlay(&self) -> Overlay<'_, T> {
+        self.overlay_with(|value| value.clone().into())
+    }
+}
+
+impl<T> Grid<T> {
+    // For cells with no char conversion, e.g. heights or enums
+    pub fn overlay_with<'a, F>(&'a self, glyph: F) -> Overlay<'a, T>
+    where
+        F: Fn(&T) -> char + 'a,
+    {
+        Overlay {
+            grid: self,
+            glyph: Box::new(glyph),
+            marks: vec![],
+        }
+    }
+
+    pub fn render_with<F>(&self, glyph: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        self.overlay_with(glyph).to_string()
+    }
+}
+
+impl<T> Overlay<'_, T> {
+    pub fn mark<I>(self, points: I, glyph: char) -> Self
+    where
+        I: IntoIterator<Item = Point2D>,
+    {
+        self.add_marks(points, glyph, None)
+    }
+
+    pub fn mark_coloured<I>(self, points: I, glyph: char, colour: Colour) -> Self
+    where
+        I: IntoIterator<Item = Point2D>,
+    {
+        self.add_marks(points, glyph, Some(colour))
+    }
+
+    // Each step is drawn as an arrow towards the next point, the final point as '@'
+    pub fn path<I>(mut self, points: I, colour: Option<Colour>) -> Self
+    where
+        I: IntoIterator<Item = Point2D>,
+    {
+        let points: Vec<Point2D> = points.into_iter().collect();
+        for (i, point) in points.iter().enumerate() {
+            let glyph = match points.get(i + 1) {
+                Some(next) => arrow(&(*next - *point)),
+                None => '@',
+            };
+            self.marks.push((*point, Mark { glyph, colour }));
+        }
+        self
+    }
+
+    fn add_marks<I>(mut self, points: I, glyph: char, colour: Option<Colour>) -> Self
+    where
+        I: IntoIterator<Item = Point2D>,
+    {
+        self.marks.extend(
+            points
+                .into_iter()
+                .map(|point| (point, Mark { glyph, colour })),
+        );
+        self
+    }
+}
+
+// Up is +y in readable coordinates
+fn arrow(diff: &Point2D) -> char {
+    match (diff.x.signum(), diff.y.signum()) {
+        (0, 1) => '^',
+        (0, -1) => 'v',
+        (1, 0) => '>',
+        (-1, 0) => '<',
+        _ => '*',
+    }
+}
+
+fn render<T>(
+    grid: &Grid<T>,
+    glyph: &dyn Fn(&T) -> char,
+    marks: &[(Point2D, Mark)],
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let mut cells: Vec<Vec<Mark>> = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|value| Mark {
+                    glyph: glyph(value),
+                    colour: None,
+                })
+                .collect()
+        })
+        .collect();
+
+    for (point, mark) in marks {
+        if grid.in_bounds(point) {
+            let (i, j) = grid.convert_coords_grid(point);
+            cells[i as usize][j as usize] = *mark;
+        }
+    }
+
+    for (i, row) in cells.iter().enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        for mark in row {
+            write!(f, "{mark}")?;
+        }
+    }
+    Ok(())
+}
+
+impl<T> fmt::Display for Grid<T>
+where
+    T: Clone + Into<char>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self, &|value: &T| value.clone().into(), &[], f)
+    }
+}
+
+impl<T> fmt::Display for Overlay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self.grid, &self.glyph, &self.marks, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let grid: Grid<char> = Grid::new(
+            "#...
+....
+...#",
+        );
+        assert_eq!(grid.to_string(), "#...\n....\n...#");
+    }
+
+    #[test]
+    fn test_overlay() {
+        let grid: Grid<char> = Grid::new(
+            "#...
+....
+...#",
+        );
+        let path = vec![
+            Point2D::new(&(0, 0)),
+            Point2D::new(&(1, 0)),
+            Point2D::new(&(1, 1)),
+            Point2D::new(&(2, 1)),
+        ];
+        let rendered = grid
+            .overlay()
+            .path(path, None)
+            .mark(vec![Point2D::new(&(3, 2)), Point2D::new(&(9, 9))], 'X')
+            .to_string();
+        let expected = "#..X
+.>@.
+>^.#";
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn test_overlay_colour() {
+        let grid: Grid<char> = Grid::new(
+            "#...
+....
+...#",
+        );
+        let rendered = grid
+            .overlay()
+            .mark_coloured(vec![Point2D::new(&(1, 2))], 'o', Colour::Red)
+            .to_string();
+        assert_eq!(rendered, "#\x1b[31mo\x1b[0m..\n....\n...#");
+    }
+
+    #[test]
+    fn test_render_with() {
+        let heights: Grid<i32> = Grid::new(
+            "012
+901",
+        );
+        let glyph = |height: &i32| char::from_digit(*height as u32, 10).unwrap();
+        assert_eq!(heights.render_with(glyph), "012\n901");
+        let rendered = heights
+            .overlay_with(|height| if *height == 9 { '#' } else { '.' })
+            .path(vec![Point2D::new(&(0, 1)), Point2D::new(&(0, 0))], None)
+            .to_string();
+        assert_eq!(rendered, "v..\n@..");
+    }
+}
@@ -0,0 +1,606 @@
+use crate::hash::FastMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// Describes a state space for the searches below. Costs are per step
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+
+    fn start_states(&self) -> Vec<Self::State>;
+
+    fn successors(&self, state: &Self::State) -> Vec<(Self::State, u64)>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    // Must never overestimate the remaining cost for A* to stay optimal. It doesn't
+    // need to be consistent, states are reopened when a cheaper route turns up
+    fn heuristic(&self, _state: &Self::State) -> u64 {
+        0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult<S> {
+    cost: u64,
+    path: Vec<S>,
+}
+
+impl<S> SearchResult<S> {
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+
+    // Start state first, goal state last
+    pub fn path(&self) -> &Vec<S> {
+        &self.path
+    }
+
+    pub fn goal(&self) -> &S {
+        self.path.last().expect("Path is never empty")
+    }
+}
+
+// Interns states so the queues and parent links can work with indices
+struct StateTable<S> {
+    ids: FastMap<S, usize>,
+    states: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> StateTable<S> {
+    fn new() -> Self {
+        Self {
+            ids: FastMap::default(),
+            states: vec![],
+        }
+    }
+
+    // Returns the id and whether the state is new
+    fn intern(&mut self, state: &S) -> (usize, bool) {
+        if let Some(id) = self.ids.get(state) {
+            return (*id, false);
+        }
+        let id = self.states.len();
+        self.ids.insert(state.clone(), id);
+        self.states.push(state.clone());
+        (id, true)
+    }
+
+    fn path_to(&self, parents: &[Option<usize>], goal: usize) -> Vec<S> {
+        let mut path: Vec<S> = vec![];
+        let mut current = Some(goal);
+        while let Some(id) = current {
+            path.push(self.states[id].clone());
+            current = parents[id];
+        }
+        path.reverse();
+        path
+    }
+}
+
+// Fewest steps to a goal, step costs are ignored and the cost is the number of steps
+pub fn bfs<P: SearchProblem>(problem: &P) -> Option<SearchResult<P::State>> {
+    let mut table: StateTable<P::State> = StateTable::new();
+    let mut parents: Vec<Option<usize>> = vec![];
+    let mut steps: Vec<u64> = vec![];
+    let mut queue: VecDeque<usize> = VecDeque::new();
+
+    for start in problem.start_states() {
+        let (id, new) = table.intern(&start);
+        if new {
+            parents.push(None);
+            steps.push(0);
+            queue.push_back(id);
+        }
+    }
+
+    while let Some(id) = queue.pop_front() {
+        let state = table.states[id].clone();
+        if problem.is_goal(&state) {
+            return Some(SearchResult {
+                cost: steps[id],
+                path: table.path_to(&parents, id),
+            });
+        }
+        for (next, _) in problem.successors(&state) {
+            let (next_id, new) = table.intern(&next);
+            if new {
+                parents.push(Some(id));
+                steps.push(steps[id] + 1);
+                queue.push_back(next_id);
+            }
+        }
+    }
+    None
+}
+
+pub fn dijkstra<P: SearchProblem>(problem: &P) -> Option<SearchResult<P::State>> {
+    best_first(problem, false)
+}
+
+pub fn astar<P: SearchProblem>(problem: &P) -> Option<SearchResult<P::State>> {
+    best_first(problem, true)
+}
+
+fn best_first<P: SearchProblem>(
+    problem: &P,
+    use_heuristic: bool,
+) -> Option<SearchResult<P::State>> {
+    let mut table: StateTable<P::State> = StateTable::new();
+    let mut parents: Vec<Option<usize>> = vec![];
+    let mut costs: Vec<u64> = vec![];
+    let mut done: Vec<bool> = vec![];
+    // (estimated total, cost so far, state id), smallest first
+    let mut heap: BinaryHeap<Reverse<(u64, u64, usize)>> = BinaryHeap::new();
+    let estimate = |state: &P::State, cost: u64| {
+        if use_heuristic {
+            cost + problem.heuristic(state)
+        } else {
+            cost
+        }
+    };
+
+    for start in problem.start_states() {
+        let (id, new) = table.intern(&start);
+        if new {
+            parents.push(None);
+            costs.push(0);
+            done.push(false);
+            heap.push(Reverse((estimate(&start, 0), 0, id)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, id))) = heap.pop() {
+        if done[id] || cost > costs[id] {
+            continue;
+        }
+        done[id] = true;
+        let state = table.states[id].clone();
+        if problem.is_goal(&state) {
+            return Some(SearchResult {
+                cost,
+                path: table.path_to(&parents, id),
+            });
+        }
+        for (next, step_cost) in problem.successors(&state) {
+            let next_cost = cost + step_cost;
+            let (next_id, new) = table.intern(&next);
+            if new {
+                parents.push(Some(id));
+                costs.push(next_cost);
+                done.push(false);
+            } else if next_cost >= costs[next_id] {
+                continue;
+            } else {
+                parents[next_id] = Some(id);
+                costs[next_id] = next_cost;
+                done[next_id] = false;
+            }
+            heap.push(Reverse((estimate(&next, next_cost), next_cost, next_id)));
+        }
+    }
+    None
+}
+
+// Every optimal route to every goal reachable at the optimal cost
+pub struct ShortestPaths<S> {
+    cost: u64,
+    states: Vec<S>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S: Clone + Eq + Hash> ShortestPaths<S> {
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+
+    pub fn goals(&self) -> Vec<S> {
+        self.goals
+            .iter()
+            .map(|id| self.states[*id].clone())
+            .collect()
+    }
+
+    // States that lie on at least one optimal path
+    pub fn states_on_paths(&self) -> HashSet<S> {
+        let mut seen: Vec<bool> = vec![false; self.states.len()];
+        let mut stack: Vec<usize> = self.goals.clone();
+        for goal in &self.goals {
+            seen[*goal] = true;
+        }
+        while let Some(id) = stack.pop() {
+            for previous in &self.predecessors[id] {
+                if !seen[*previous] {
+                    seen[*previous] = true;
+                    stack.push(*previous);
+                }
+            }
+        }
+        (0..self.states.len())
+            .filter(|id| seen[*id])
+            .map(|id| self.states[id].clone())
+            .collect()
+    }
+
+    pub fn count_paths(&self) -> u64 {
+        let mut counts: Vec<Option<u64>> = vec![None; self.states.len()];
+        self.goals
+            .iter()
+            .map(|goal| self.count_paths_to(*goal, &mut counts))
+            .sum()
+    }
+
+    // Enumerates every optimal path, which can be exponentially many
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        let mut results: Vec<Vec<S>> = vec![];
+        for goal in &self.goals {
+            self.collect_paths(*goal, &mut results);
+        }
+        results
+    }
+
+    // Walks back with an explicit stack, paths can be far longer than the call stack
+    fn count_paths_to(&self, goal: usize, counts: &mut [Option<u64>]) -> u64 {
+        let mut stack: Vec<usize> = vec![goal];
+        while let Some(&id) = stack.last() {
+            if counts[id].is_some() {
+                stack.pop();
+                continue;
+            }
+            // A state is summed once every predecessor has its count
+            let waiting = stack.len();
+            stack.extend(
+                self.predecessors[id]
+                    .iter()
+                    .filter(|previous| counts[**previous].is_none()),
+            );
+            if stack.len() == waiting {
+                let count = if self.predecessors[id].is_empty() {
+                    1
+                } else {
+                    self.predecessors[id]
+                        .iter()
+                        .map(|previous| counts[*previous].expect("Predecessor is counted"))
+                        .sum()
+                };
+                counts[id] = Some(count);
+                stack.pop();
+            }
+        }
+        counts[goal].expect("Goal is counted")
+    }
+
+    // The stack holds the current path back from the goal, each entry with the
+    // index of the next predecessor to try
+    fn collect_paths(&self, goal: usize, results: &mut Vec<Vec<S>>) {
+        let mut stack: Vec<(usize, usize)> = vec![(goal, 0)];
+        while let Some(&(id, next)) = stack.last() {
+            if next == 0 && self.predecessors[id].is_empty() {
+                results.push(
+                    stack
+                        .iter()
+                        .rev()
+                        .map(|(id, _)| self.states[*id].clone())
+                        .collect(),
+                );
+            }
+            match self.predecessors[id].get(next) {
+                Some(previous) => {
+                    stack.last_mut().expect("Stack is not empty").1 += 1;
+                    stack.push((*previous, 0));
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
+
+// Panics if zero cost steps form a cycle, as there would be endless optimal paths
+pub fn all_shortest_paths<P: SearchProblem>(problem: &P) -> Option<ShortestPaths<P::State>> {
+    let mut table: StateTable<P::State> = StateTable::new();
+    let mut predecessors: Vec<Vec<usize>> = vec![];
+    let mut costs: Vec<u64> = vec![];
+    let mut done: Vec<bool> = vec![];
+    let mut heap: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+    let mut best: Option<u64> = None;
+    let mut goals: Vec<usize> = vec![];
+
+    for start in problem.start_states() {
+        let (id, new) = table.intern(&start);
+        if new {
+            predecessors.push(vec![]);
+            costs.push(0);
+            done.push(false);
+            heap.push(Reverse((0, id)));
+        }
+    }
+
+    while let Some(Reverse((cost, id))) = heap.pop() {
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+        if done[id] || cost > costs[id] {
+            continue;
+        }
+        done[id] = true;
+        let state = table.states[id].clone();
+        if problem.is_goal(&state) {
+            best = Some(cost);
+            goals.push(id);
+            continue;
+        }
+        for (next, step_cost) in problem.successors(&state) {
+            let next_cost = cost + step_cost;
+            let (next_id, new) = table.intern(&next);
+            if new {
+                predecessors.push(vec![id]);
+                costs.push(next_cost);
+                done.push(false);
+            } else if next_cost > costs[next_id] {
+                continue;
+            } else if next_cost == costs[next_id] {
+                // Only a zero cost step can reach a finished state at the same cost
+                if done[next_id] && leads_to(&predecessors, next_id, id) {
+                    panic!("Zero cost cycle on a shortest path");
+                }
+                predecessors[next_id].push(id);
+                continue;
+            } else {
+                predecessors[next_id] = vec![id];
+                costs[next_id] = next_cost;
+            }
+            heap.push(Reverse((next_cost, next_id)));
+        }
+    }
+
+    best.map(|cost| ShortestPaths {
+        cost,
+        states: table.states,
+        predecessors,
+        goals,
+    })
+}
+
+// Whether following predecessor links back from id ever reaches target
+fn leads_to(predecessors: &[Vec<usize>], target: usize, id: usize) -> bool {
+    let mut seen: Vec<bool> = vec![false; predecessors.len()];
+    let mut stack: Vec<usize> = vec![id];
+    while let Some(current) = stack.pop() {
+        if current == target {
+            return true;
+        }
+        for previous in &predecessors[current] {
+            if !seen[*previous] {
+                seen[*previous] = true;
+                stack.push(*previous);
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Direction, Grid, Point2D};
+
+    // Walls are '#', moving costs 1 and turning costs 1000 like the reindeer maze
+    struct Maze {
+        grid: Grid<char>,
+        turn_cost: u64,
+    }
+
+    impl SearchProblem for Maze {
+        type State = (Point2D, Direction);
+
+        fn start_states(&self) -> Vec<Self::State> {
+            vec![(self.grid.find(&'S').unwrap(), Direction::Right)]
+        }
+
+        fn successors(&self, state: &Self::State) -> Vec<(Self::State, u64)> {
+            let (point, direction) = *state;
+            let mut result = vec![
+                ((point, direction.turn_left()), self.turn_cost),
+                ((point, direction.turn_right()), self.turn_cost),
+            ];
+            let next = point + direction.offset();
+            if self.grid.get(&next).is_some_and(|c| *c != '#') {
+                result.push(((next, direction), 1));
+            }
+            result
+        }
+
+        fn is_goal(&self, state: &Self::State) -> bool {
+            *self.grid.access_grid(&state.0) == 'E'
+        }
+
+        fn heuristic(&self, state: &Self::State) -> u64 {
+            state.0.manhattan(&self.grid.find(&'E').unwrap()) as u64
+        }
+    }
+
+    // Unit cost steps between open cells
+    struct Walk {
+        grid: Grid<char>,
+    }
+
+    impl SearchProblem for Walk {
+        type State = Point2D;
+
+        fn start_states(&self) -> Vec<Self::State> {
+            vec![self.grid.find(&'S').unwrap()]
+        }
+
+        fn successors(&self, state: &Self::State) -> Vec<(Self::State, u64)> {
+            self.grid
+                .neighbours(state)
+                .filter(|next| *self.grid.access_grid(next) != '#')
+                .map(|next| (next, 1))
+                .collect()
+        }
+
+        fn is_goal(&self, state: &Self::State) -> bool {
+            *self.grid.access_grid(state) == 'E'
+        }
+    }
+
+    // Explicit edge list between named states, with an optional heuristic per state
+    struct Graph {
+        edges: Vec<(char, char, u64)>,
+        estimates: Vec<(char, u64)>,
+    }
+
+    impl SearchProblem for Graph {
+        type State = char;
+
+        fn start_states(&self) -> Vec<Self::State> {
+            vec!['S']
+        }
+
+        fn successors(&self, state: &Self::State) -> Vec<(Self::State, u64)> {
+            self.edges
+                .iter()
+                .filter(|(from, _, _)| from == state)
+                .map(|(_, to, cost)| (*to, *cost))
+                .collect()
+        }
+
+        fn is_goal(&self, state: &Self::State) -> bool {
+            *state == 'G'
+        }
+
+        fn heuristic(&self, state: &Self::State) -> u64 {
+            self.estimates
+                .iter()
+                .find(|(named, _)| named == state)
+                .map_or(0, |(_, estimate)| *estimate)
+        }
+    }
+
+    fn maze(turn_cost: u64) -> Maze {
+        Maze {
+            grid: Grid::new(
+                "#######
+#....E#
+#.#.#.#
+#.....#
+#S#####
+#######",
+            ),
+            turn_cost,
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs(&maze(1000)).unwrap();
+        // Turning counts as a step for BFS
+        assert_eq!(result.cost(), 9);
+        assert_eq!(result.path()[0].0, Point2D::new(&(1, 1)));
+        assert_eq!(result.goal().0, Point2D::new(&(5, 4)));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let dijkstra_result = dijkstra(&maze(1000)).unwrap();
+        let astar_result = astar(&maze(1000)).unwrap();
+        assert_eq!(dijkstra_result.cost(), 2007);
+        assert_eq!(astar_result.cost(), 2007);
+        assert_eq!(dijkstra(&maze(0)).unwrap().cost(), 7);
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        // h(A) never overestimates but does overshoot h(B) + 1, so B is first
+        // reached through the dearer edge and has to be reopened
+        let graph = Graph {
+            edges: vec![('S', 'A', 1), ('S', 'B', 3), ('A', 'B', 1), ('B', 'G', 10)],
+            estimates: vec![('A', 11)],
+        };
+        assert_eq!(dijkstra(&graph).unwrap().cost(), 12);
+        let result = astar(&graph).unwrap();
+        assert_eq!(result.cost(), 12);
+        assert_eq!(result.path(), &vec!['S', 'A', 'B', 'G']);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let paths = all_shortest_paths(&maze(1000)).unwrap();
+        assert_eq!(paths.cost(), 2007);
+        assert_eq!(paths.count_paths(), 1);
+        let tiles: HashSet<Point2D> = paths
+            .states_on_paths()
+            .iter()
+            .map(|(point, _)| *point)
+            .collect();
+        assert_eq!(tiles.len(), 8);
+
+        // Corner to corner of an open 3x3 has 6 tied routes covering every cell
+        let open = Walk {
+            grid: Grid::new(
+                "..E
+...
+S..",
+            ),
+        };
+        let paths = all_shortest_paths(&open).unwrap();
+        assert_eq!(paths.cost(), 4);
+        assert_eq!(paths.count_paths(), 6);
+        assert_eq!(paths.paths().len(), 6);
+        assert!(paths.paths().iter().all(|path| path.len() == 5));
+        assert_eq!(paths.states_on_paths().len(), 9);
+        assert_eq!(paths.goals(), vec![Point2D::new(&(2, 2))]);
+    }
+
+    #[test]
+    fn test_zero_cost_ties() {
+        // C is finished through the direct edge before B offers the tied route
+        let graph = Graph {
+            edges: vec![('S', 'C', 0), ('S', 'B', 0), ('B', 'C', 0), ('C', 'G', 1)],
+            estimates: vec![],
+        };
+        let paths = all_shortest_paths(&graph).unwrap();
+        assert_eq!(paths.cost(), 1);
+        assert_eq!(paths.count_paths(), 2);
+        let mut routes = paths.paths();
+        routes.sort();
+        assert_eq!(routes, vec![vec!['S', 'B', 'C', 'G'], vec!['S', 'C', 'G']]);
+        assert_eq!(paths.states_on_paths().len(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_cost_cycle() {
+        let graph = Graph {
+            edges: vec![('S', 'A', 0), ('A', 'B', 0), ('B', 'A', 0), ('B', 'G', 1)],
+            estimates: vec![],
+        };
+        all_shortest_paths(&graph);
+    }
+
+    #[test]
+    fn test_long_corridor() {
+        // Far deeper than the call stack would allow if the walks recursed
+        let corridor = Walk {
+            grid: Grid::new(&format!("S{}E", ".".repeat(20_000))),
+        };
+        let paths = all_shortest_paths(&corridor).unwrap();
+        assert_eq!(paths.cost(), 20_001);
+        assert_eq!(paths.count_paths(), 1);
+        let routes = paths.paths();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].len(), 20_002);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let mut blocked = maze(1000);
+        blocked.grid = Grid::new("#S#E#");
+        assert!(bfs(&blocked).is_none());
+        assert!(dijkstra(&blocked).is_none());
+        assert!(all_shortest_paths(&blocked).is_none());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::hash::FastMap;
+use crate::{Direction, Grid, GridRead, Point2D, Rect};
+
+// Unbounded grid that only stores the cells that have been set
+pub struct SparseGrid<T> {
+    cells: FastMap<Point2D, T>,
+    // Bottom left and top right corners of everything stored
+    bounds: Option<(Point2D, Point2D)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: FastMap::default(),
+            bounds: None,
+        }
+    }
+
+    pub fn insert(&mut self, point: &Point2D, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point2D::new(&(min.x.min(point.x), min.y.min(point.y))),
+                Point2D::new(&(max.x.max(point.x), max.y.max(point.y))),
+            ),
+            None => (*point, *point),
+        });
+        self.cells.insert(*point, value)
+    }
+
+    pub fn remove(&mut self, point: &Point2D) -> Option<T> {
+        let removed = self.cells.remove(point);
+        if let (Some(_), Some((min, max))) = (&removed, self.bounds) {
+            // Only shrinks if the point was on the edge of the bounding box
+            if point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y {
+                self.recompute_bounds();
+            }
+        }
+        removed
+    }
+
+    pub fn contains(&self, point: &Point2D) -> bool {
+        self.cells.contains_key(point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+            .map(|(min, max)| Rect::new(&min, max.x - min.x + 1, max.y - min.y + 1))
+    }
+
+    // Dense copy of the bounding box, gaps are filled in with fill
+    pub fn to_grid(&self, fill: &T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some((min, max)) = self.bounds else {
+            panic!("Sparse grid is empty");
+        };
+        Grid::from_rows(
+            (min.y..=max.y)
+                .rev()
+                .map(|y| {
+                    (min.x..=max.x)
+                        .map(|x| {
+                            self.cells
+                                .get(&Point2D::new(&(x, y)))
+                                .unwrap_or(fill)
+                                .clone()
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    fn recompute_bounds(&mut self) {
+        self.bounds = None;
+        for point in self.cells.keys() {
+            self.bounds = Some(match self.bounds {
+                Some((min, max)) => (
+                    Point2D::new(&(min.x.min(point.x), min.y.min(point.y))),
+                    Point2D::new(&(max.x.max(point.x), max.y.max(point.y))),
+                ),
+                None => (*point, *point),
+            });
+        }
+    }
+}
+
+impl<T> GridRead<T> for SparseGrid<T> {
+    fn get(&self, point: &Point2D) -> Option<&T> {
+        self.cells.get(point)
+    }
+
+    // Inside the current bounding box
+    fn in_bounds(&self, point: &Point2D) -> bool {
+        match self.bounds() {
+            Some(rect) => rect.contains(point),
+            None => false,
+        }
+    }
+
+    // Same order as Grid, top row first then left to right
+    fn enumerate<'a>(&'a self) -> impl Iterator<Item = (Point2D, &'a T)> + 'a
+    where
+        T: 'a,
+    {
+        let mut cells: Vec<(Point2D, &T)> = self
+            .cells
+            .iter()
+            .map(|(point, value)| (*point, value))
+            .collect();
+        cells.sort_by_key(|(point, _)| (-point.y, point.x));
+        cells.into_iter()
+    }
+
+    // The grid is unbounded so every neighbour is valid
+    fn neighbours(&self, point: &Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        let point = *point;
+        Direction::CARDINALS
+            .iter()
+            .map(move |direction| point + direction.offset())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid: SparseGrid<char> = SparseGrid::new();
+        assert!(grid.bounds().is_none());
+
+        grid.insert(&Point2D::new(&(-2, 1)), 'a');
+        grid.insert(&Point2D::new(&(1, -1)), 'b');
+        grid.insert(&Point2D::new(&(0, 0)), 'c');
+        assert_eq!(grid.len(), 3);
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(&Point2D::new(&(-2, -1)), 4, 3))
+        );
+        assert_eq!(grid.to_grid(&'.').to_string(), "a...\n..c.\n...b");
+        assert_eq!(grid.find(&'b'), Some(Point2D::new(&(1, -1))));
+        assert_eq!(grid.get(&Point2D::new(&(5, 5))), None);
+
+        let order: Vec<char> = grid.enumerate().map(|(_, c)| *c).collect();
+        assert_eq!(order, vec!['a', 'c', 'b']);
+
+        assert_eq!(grid.remove(&Point2D::new(&(-2, 1))), Some('a'));
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(&Point2D::new(&(0, -1)), 2, 2))
+        );
+        assert!(!grid.in_bounds(&Point2D::new(&(-1, 0))));
+
+        // Rays and lines stop at the bounding box, gaps included
+        grid.insert(&Point2D::new(&(2, 1)), 'c');
+        let ray: Vec<Point2D> = grid
+            .ray(&Point2D::new(&(0, 0)), &Point2D::new(&(1, 0)))
+            .collect();
+        assert_eq!(ray.len(), 3);
+        let line: Vec<Option<&char>> = grid
+            .line(&Point2D::new(&(0, -3)), &Point2D::new(&(2, 1)))
+            .map(|point| grid.get(&point))
+            .collect();
+        assert_eq!(line, vec![Some(&'b'), Some(&'c')]);
+        let groups = grid.group_by_value();
+        assert_eq!(
+            groups[&'c'],
+            vec![Point2D::new(&(2, 1)), Point2D::new(&(0, 0))]
+        );
+        let found: Vec<Point2D> = grid.positions(|c| *c == 'b').collect();
+        assert_eq!(found, vec![Point2D::new(&(1, -1))]);
+    }
+}
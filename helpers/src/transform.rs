@@ -0,0 +1,206 @@
+use crate::{Grid, Point2D};
+
+// Rectangle in readable coordinates, origin is the bottom left cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    origin: Point2D,
+    width: i32,
+    height: i32,
+}
+
+impl Rect {
+    pub fn new(origin: &Point2D, width: i32, height: i32) -> Self {
+        if width <= 0 || height <= 0 {
+            panic!("Rect must have a positive size");
+        }
+        Self {
+            origin: *origin,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, point: &Point2D) -> bool {
+        let offset = *point - self.origin;
+        offset.x >= 0 && offset.x < self.width && offset.y >= 0 && offset.y < self.height
+    }
+}
+
+// Borrowed window onto part of a grid, (0,0) is the bottom left of the rect
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
+}
+
+impl<T> Grid<T>
+where
+    T: Clone,
+{
+    // Rotations and flips are as the grid is printed, top row first
+    pub fn rotate_cw(&self) -> Self {
+        let (height, width) = (self.grid.len(), self.grid[0].len());
+        Self::from_rows(
+            (0..width)
+                .map(|i| {
+                    (0..height)
+                        .map(|j| self.grid[height - 1 - j][i].clone())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        let (height, width) = (self.grid.len(), self.grid[0].len());
+        Self::from_rows(
+            (0..width)
+                .map(|i| {
+                    (0..height)
+                        .map(|j| self.grid[j][width - 1 - i].clone())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    // Mirrors along the top left to bottom right diagonal
+    pub fn transpose(&self) -> Self {
+        let (height, width) = (self.grid.len(), self.grid[0].len());
+        Self::from_rows(
+            (0..width)
+                .map(|i| (0..height).map(|j| self.grid[j][i].clone()).collect())
+                .collect(),
+        )
+    }
+
+    // Left becomes right
+    pub fn flip_h(&self) -> Self {
+        Self::from_rows(
+            self.grid
+                .iter()
+                .map(|row| row.iter().rev().cloned().collect())
+                .collect(),
+        )
+    }
+
+    // Top becomes bottom
+    pub fn flip_v(&self) -> Self {
+        Self::from_rows(self.grid.iter().rev().cloned().collect())
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn view(&self, rect: &Rect) -> GridView<'_, T> {
+        let far_corner = rect.origin + Point2D::new(&(rect.width - 1, rect.height - 1));
+        if !self.in_bounds(&rect.origin) || !self.in_bounds(&far_corner) {
+            panic!("View is outside the grid");
+        }
+        GridView {
+            grid: self,
+            rect: *rect,
+        }
+    }
+}
+
+impl<T> GridView<'_, T> {
+    pub fn width(&self) -> i32 {
+        self.rect.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.rect.height
+    }
+
+    pub fn in_bounds(&self, point: &Point2D) -> bool {
+        point.x >= 0 && point.x < self.rect.width && point.y >= 0 && point.y < self.rect.height
+    }
+
+    pub fn access_grid(&self, coords: &Point2D) -> &T {
+        if !self.in_bounds(coords) {
+            panic!("Point is outside the view");
+        }
+        self.grid.access_grid(&(self.rect.origin + *coords))
+    }
+
+    // Every cell alongside its coordinates within the view, top row first
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point2D, &T)> + '_ {
+        (0..self.rect.height).rev().flat_map(move |y| {
+            (0..self.rect.width).map(move |x| {
+                let point = Point2D::new(&(x, y));
+                (point, self.access_grid(&point))
+            })
+        })
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_rows(
+            (0..self.rect.height)
+                .rev()
+                .map(|y| {
+                    (0..self.rect.width)
+                        .map(|x| self.access_grid(&Point2D::new(&(x, y))).clone())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotations() {
+        let grid: Grid<char> = Grid::new(
+            "abc
+def",
+        );
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw().to_string(), grid.to_string());
+    }
+
+    #[test]
+    fn test_flips() {
+        let grid: Grid<char> = Grid::new(
+            "abc
+def",
+        );
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.flip_h().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_v().to_string(), "def\nabc");
+    }
+
+    #[test]
+    fn test_view() {
+        let grid: Grid<char> = Grid::new(
+            "abcd
+efgh
+ijkl",
+        );
+        let view = grid.view(&Rect::new(&Point2D::new(&(1, 0)), 2, 2));
+        assert_eq!(view.width(), 2);
+        assert_eq!(view.height(), 2);
+        assert_eq!(*view.access_grid(&Point2D::new(&(0, 0))), 'j');
+        assert_eq!(*view.access_grid(&Point2D::new(&(1, 1))), 'g');
+        assert!(!view.in_bounds(&Point2D::new(&(2, 0))));
+        assert_eq!(view.to_grid().to_string(), "fg\njk");
+
+        let cells: Vec<char> = view.enumerate().map(|(_, c)| *c).collect();
+        assert_eq!(cells, vec!['f', 'g', 'j', 'k']);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_view_outside_grid() {
+        let grid: Grid<char> = Grid::new(
+            "abc
+def",
+        );
+        grid.view(&Rect::new(&Point2D::new(&(2, 0)), 2, 1));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::math::gcd;
+use crate::{Direction, Grid, GridRead, Point2D};
+
+// Grid on a torus, coordinates off one edge come back on the opposite edge
+pub struct WrappingGrid<T> {
+    grid: Grid<T>,
+}
+
+impl<T> WrappingGrid<T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    pub fn inner(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn width(&self) -> i32 {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> i32 {
+        self.grid.height()
+    }
+
+    pub fn wrap(&self, point: &Point2D) -> Point2D {
+        Point2D::new(&(
+            point.x.rem_euclid(self.width()),
+            point.y.rem_euclid(self.height()),
+        ))
+    }
+
+    pub fn access_grid(&self, point: &Point2D) -> &T {
+        self.grid.access_grid(&self.wrap(point))
+    }
+}
+
+impl<T> GridRead<T> for WrappingGrid<T> {
+    fn get(&self, point: &Point2D) -> Option<&T> {
+        Some(self.access_grid(point))
+    }
+
+    fn in_bounds(&self, _point: &Point2D) -> bool {
+        true
+    }
+
+    fn enumerate<'a>(&'a self) -> impl Iterator<Item = (Point2D, &'a T)> + 'a
+    where
+        T: 'a,
+    {
+        self.grid.enumerate()
+    }
+
+    // Always four neighbours, already wrapped back onto the grid
+    fn neighbours(&self, point: &Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        let point = *point;
+        Direction::CARDINALS
+            .iter()
+            .map(move |direction| self.wrap(&(point + direction.offset())))
+    }
+
+    // Stops just before it comes back round to the wrapped start
+    fn ray<'a>(
+        &'a self,
+        start: &Point2D,
+        step: &Point2D,
+    ) -> impl Iterator<Item = Point2D> + use<'a, T> {
+        if step.x == 0 && step.y == 0 {
+            panic!("Ray step can't be zero");
+        }
+        let (first, step) = (self.wrap(start), *step);
+        std::iter::successors(Some(first), move |point| {
+            let next = self.wrap(&(*point + step));
+            (next != first).then_some(next)
+        })
+    }
+
+    // Same points as on a plain grid, each wrapped back onto the grid
+    fn line<'a>(
+        &'a self,
+        start: &Point2D,
+        end: &Point2D,
+    ) -> impl Iterator<Item = Point2D> + use<'a, T> {
+        let diff = *end - *start;
+        let step = diff.primitive();
+        let steps = gcd(diff.x, diff.y);
+        let start = *start;
+        (0..=steps).map(move |i| self.wrap(&(start + step * i)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapping() {
+        let grid = WrappingGrid::new(Grid::<char>::new(
+            "abc
+def",
+        ));
+        assert_eq!(grid.wrap(&Point2D::new(&(-1, 2))), Point2D::new(&(2, 0)));
+        assert_eq!(*grid.access_grid(&Point2D::new(&(3, 1))), 'a');
+        assert_eq!(grid.get(&Point2D::new(&(-7, -4))), Some(&'f'));
+        assert!(grid.in_bounds(&Point2D::new(&(100, 100))));
+
+        let neighbours: Vec<Point2D> = grid.neighbours(&Point2D::new(&(0, 0))).collect();
+        assert_eq!(
+            neighbours,
+            vec![
+                Point2D::new(&(0, 1)),
+                Point2D::new(&(1, 0)),
+                Point2D::new(&(0, 1)),
+                Point2D::new(&(2, 0)),
+            ]
+        );
+        assert_eq!(grid.find(&'e'), Some(Point2D::new(&(1, 0))));
+
+        // Stepping right twice from 'a' visits every cell in the bottom row once
+        let ray: Vec<char> = grid
+            .ray(&Point2D::new(&(0, 0)), &Point2D::new(&(2, 0)))
+            .map(|point| *grid.access_grid(&point))
+            .collect();
+        assert_eq!(ray, vec!['d', 'f', 'e']);
+        let line: Vec<Point2D> = grid
+            .line(&Point2D::new(&(2, 0)), &Point2D::new(&(4, 2)))
+            .collect();
+        assert_eq!(
+            line,
+            vec![
+                Point2D::new(&(2, 0)),
+                Point2D::new(&(0, 1)),
+                Point2D::new(&(1, 0)),
+            ]
+        );
+        let groups = grid.group_by_value();
+        assert_eq!(groups[&'b'], vec![Point2D::new(&(1, 1))]);
+    }
+}
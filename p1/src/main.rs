@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use helpers::Counter;
 use std::fs::File;
 use std::io::{self, BufRead};
 
@@ -28,22 +28,8 @@ fn solution_one<'a>(mut left: Vec<i128>, mut right: Vec<i128>) -> i128 {
 }
 
 fn solution_two<'a>( left: Vec<i128>, right: Vec<i128>) -> i128 {
-    let mut result = 0;
-
-    let mut right_map = HashMap::new();
-
-    for num in right {
-        let count = right_map.entry(num).or_insert(0);
-        *count += 1;
-    }
-
-    for num in left {
-        if let Some(count) = right_map.get(&num) {
-            result += num * count;
-        }
-    }
-
-    result
+    let right_counts: Counter<i128> = right.into_iter().collect();
+    left.iter().map(|num| num * right_counts.count(num) as i128).sum()
 }
 
 fn get_lists() -> (Vec<i128>, Vec<i128>) {
@@ -1,115 +1,37 @@
 use helpers::{Grid, Point2D};
-use std::collections::HashMap;
-
-#[derive(Debug, PartialEq, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
 
 struct ReindeerMap {
     grid: Grid<i32>,
     start_points: Vec<Point2D>,
-    end_points: Vec<Point2D>,
-    scores: HashMap<i32, i32>,
 }
 
 impl ReindeerMap {
-    fn new(input: &String) -> Self {
-        let mut start_points: Vec<Point2D> = vec![];
-        let mut end_points: Vec<Point2D> = vec![];
+    fn new(input: &str) -> Self {
         let grid = Grid::new(input);
+        let start_points: Vec<Point2D> = grid.positions(|height| *height == 0).collect();
 
-        for (j, line) in grid.iter().enumerate() {
-            for (i, value) in line.iter().enumerate() {
-                let point: Point2D = grid.convert_coords_readable(&(i as i32, j as i32));
-                if *grid.access_grid(&point) == 0 {
-                    start_points.push(point);
-                } else if *grid.access_grid(&point) == 9 {
-                    end_points.push(point);
-                }
-            }
-        }
-
-        Self {
-            grid,
-            start_points,
-            end_points,
-            scores: HashMap::new(),
-        }
-    }
-
-    // Goes from every end point and if it reaches a valid point it will increase that locations score by one
-    fn assign_scores(&mut self) {
-        for nine in self.end_points.clone() {
-            self.step_and_branch(&nine, &Direction::Up, &true);
-        }
-    }
-
-    fn collect_scores(&self) -> i32 {
-        let mut result = 0;
-        for zero in &self.start_points {
-            let id = self.grid.point_to_int(zero);
-            match self.scores.get(&id) {
-                Some(val) => {
-                    dbg!(&zero);
-                    dbg!(&id);
-                    result += val;
-                }
-                _ => panic!(),
-            }
-        }
-        result
-    }
-
-    fn can_step(&self, cur_loc: &Point2D, dir: &Direction) -> Result<Point2D, &str> {
-        let next_pos = match dir {
-            Direction::Up => *cur_loc + Point2D::new(&(0, 1)),
-            Direction::Right => *cur_loc + Point2D::new(&(1, 0)),
-            Direction::Left => *cur_loc + Point2D::new(&(-1, 0)),
-            Direction::Down => *cur_loc + Point2D::new(&(0, -1)),
-        };
-        if !self.grid.in_bounds(&next_pos) {
-            return Err("Next point out of bounds");
-        }
-
-        let cur_height = self.grid.access_grid(&cur_loc);
-        if *self.grid.access_grid(&next_pos) != cur_height - 1 {
-            return Err("Next pos is too low");
-        }
-        return Ok(next_pos);
+        Self { grid, start_points }
     }
 
-    fn step_and_branch(&mut self, position: &Point2D, from: &Direction, start: &bool) {
-        *self
-            .scores
-            .entry(self.grid.point_to_int(position))
-            .or_insert(0) += 1;
-        let dirs = vec![
-            Direction::Up,
-            Direction::Down,
-            Direction::Left,
-            Direction::Right,
-        ];
-        if *start {
-            dirs.iter().filter_map(|dir| Some(*dir != *from));
-        }
-        for dir in dirs {
-            if let Ok(next_pos) = self.can_step(&position, &dir) {
-                self.step_and_branch(&next_pos, &dir, &false);
-            };
-        }
+    // Number of nines reachable from the trailhead by climbing one step at a time
+    fn trailhead_score(&self, start: &Point2D) -> i32 {
+        self.grid
+            .flood_fill(start, |from, to| *to == from + 1)
+            .cells()
+            .iter()
+            .filter(|point| *self.grid.access_grid(point) == 9)
+            .count() as i32
     }
 }
 
-pub fn solution_one(input: &String) -> i32 {
-    let mut map = ReindeerMap::new(&input);
-    map.assign_scores();
-    map.collect_scores()
+pub fn solution_one(input: &str) -> i32 {
+    let map = ReindeerMap::new(input);
+    map.start_points
+        .iter()
+        .map(|start| map.trailhead_score(start))
+        .sum()
 }
-pub fn solution_two(input: &String) -> i32 {
+pub fn solution_two(input: &str) -> i32 {
     0
 }
 
@@ -1,19 +1,19 @@
 // use test_helpers;
-use std::cmp::Ordering;
-use std::collections::HashMap;
+use helpers::graph::DiGraph;
+use helpers::hash::FastMap;
 
 struct Ruleset {
-    rules: HashMap<i32, Vec<i32>>,
+    rules: FastMap<i32, Vec<i32>>,
 }
 
 impl Ruleset {
-    fn new_from_map(map: HashMap<i32, Vec<i32>>) -> Self {
+    fn new_from_map(map: FastMap<i32, Vec<i32>>) -> Self {
         Self { rules: map.clone() }
     }
 
     fn new() -> Self {
         Self {
-            rules: HashMap::new(),
+            rules: FastMap::default(),
         }
     }
 
@@ -44,12 +44,12 @@ impl Ruleset {
         }
     }
 
-    fn custom_order(&self, first: &i32, second: &i32) -> Ordering {
-        if self.check_order(first, second) {
-            Ordering::Less
-        } else {
-            Ordering::Greater
-        }
+    // Edge from each page to every page that must come after it
+    fn graph(&self) -> DiGraph<i32> {
+        self.rules
+            .iter()
+            .flat_map(|(smaller, larger)| larger.iter().map(|larger| (*smaller, *larger)))
+            .collect()
     }
 }
 
@@ -124,9 +124,10 @@ pub fn solution_two(input: &String) -> i32 {
         .filter(|page_list| !check_list(&page_list, &rules))
         .collect();
 
+    let graph = rules.graph();
     let sorted_pages: Vec<Vec<i32>> = incorrect_pages
         .into_iter()
-        .map(|list| correct_list(&list, &rules))
+        .map(|list| correct_list(&list, &graph))
         .collect();
 
     for list in sorted_pages {
@@ -137,10 +138,15 @@ pub fn solution_two(input: &String) -> i32 {
     result
 }
 
-fn correct_list(list: &Vec<i32>, rule_set: &Ruleset) -> Vec<i32> {
-    let mut sorted: Vec<i32> = list.clone();
-    sorted.sort_by(|first, second| rule_set.custom_order(first, second));
-    sorted
+// The full ruleset can have cycles but the rules between the pages of one update can't
+fn correct_list(list: &Vec<i32>, graph: &DiGraph<i32>) -> Vec<i32> {
+    let mut pages = graph.subgraph(list);
+    for page in list {
+        pages.add_node(page);
+    }
+    pages
+        .topological_sort()
+        .expect("Rules for an update form a cycle")
 }
 
 #[cfg(test)]
@@ -215,7 +221,7 @@ mod tests {
         .into_iter()
         .map(String::from)
         .collect();
-        let expected: HashMap<i32, Vec<i32>> = [
+        let expected: FastMap<i32, Vec<i32>> = [
             (47, vec![53, 13, 61, 29]),
             (97, vec![13, 61, 47, 29, 53, 75]),
             (75, vec![29, 53, 47, 61, 13]),
@@ -361,7 +367,7 @@ mod tests {
             vec![97, 75, 47, 29, 13],
         ];
         for (i, input_val) in input.iter().enumerate() {
-            assert_eq!(correct_list(&input_val, &rule_set), expected[i]);
+            assert_eq!(correct_list(&input_val, &rule_set.graph()), expected[i]);
         }
     }
 
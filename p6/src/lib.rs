@@ -1,11 +1,9 @@
-use helpers::{Grid, Point2D};
-use std::collections::HashMap;
+use helpers::{Direction, DirectionalBitGrid, Grid, Point2D};
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 enum Location {
-    Visited,
     Obstacle,
     Open,
     OutOfBounds,
@@ -34,175 +32,74 @@ impl fmt::Display for ParseLocationError {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-struct ParseDirectionError;
-impl fmt::Display for ParseDirectionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Invalid character for Direction")
-    }
-}
-
-impl FromStr for Direction {
-    type Err = ParseDirectionError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "^" => Ok(Direction::Up),
-            ">" => Ok(Direction::Right),
-            "<" => Ok(Direction::Left),
-            "v" => Ok(Direction::Down),
-            _ => Err(ParseDirectionError),
-        }
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Map {
-    guard_pos: (i32, i32),
+    guard_pos: Point2D,
     grid: Grid<Location>,
     direction: Direction,
+    visited: DirectionalBitGrid,
     cyclic: bool,
 }
 
 impl Map {
-    fn new(input: &String) -> Self {
-        let mut direction: Direction = Direction::Up;
-        let mut pos = Point2D::new(0, 0);
-        let grid = Grid::new(input);
-
-        for (i, line) in input.lines().enumerate() {
-            for (j, c) in line.chars().enumerate() {
-                match c {
-                    '^','>','v','<' => {
-                        direction = c.parse()?;
-                        pos = self.grid.convert_coords_readable(i as i32, j as i32);
-                    }
-                    _ => {}
-                }
-            }
-        }
+    fn new(input: &str) -> Self {
+        let grid: Grid<Location> = Grid::new(input);
+        let guard_grid: Grid<char> = Grid::new(input);
+        let (guard_pos, guard) = guard_grid
+            .enumerate()
+            .find(|(_, c)| matches!(c, '^' | '>' | 'v' | '<'))
+            .expect("No guard on the map");
+        let direction = Direction::try_from(*guard).expect("Invalid guard");
+        let visited = DirectionalBitGrid::new(grid.width(), grid.height());
 
         Self {
-            grid: Grid::new(input),
+            grid,
             direction,
-            guard_pos: pos,
+            guard_pos,
+            visited,
             cyclic: false,
         }
     }
 
-    fn obstruct(&mut self, position: (i32, i32)) {
-        self.grid[position.1 as usize][position.0 as usize] = Location::Obstacle
+    fn obstruct(&mut self, position: &Point2D) {
+        *self.grid.access_grid_mut(position) = Location::Obstacle
     }
 
     fn out_of_bounds(&self) -> bool {
-        let (y, x) = self.guard_pos;
-        let grid_height = self.grid.len() as i32;
-        if grid_height == 0 {
-            panic!("Map is empty");
-        }
-        let grid_width = self.grid[0].len() as i32;
-        if grid_width == 0 {
-            panic!("Map rows are empty");
-        }
-        y >= grid_height || x >= grid_width || y < 0 || x < 0
+        !self.grid.in_bounds(&self.guard_pos)
     }
 
     fn rotate_direction(&mut self) {
-        self.direction = match self.direction {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        };
-    }
-
-    fn move_guard(&self) -> (i32, i32) {
-        match self.direction {
-            Direction::Up => (0, -1),
-            Direction::Right => (1, 0),
-            Direction::Down => (0, 1),
-            Direction::Left => (-1, 0),
-        }
+        self.direction = self.direction.turn_right();
     }
 
     fn search_ahead(&self) -> Location {
-        let next_move = self.move_guard();
-        let next_pos = (
-            self.guard_pos.0 + next_move.0,
-            self.guard_pos.1 + next_move.1,
-        );
-        if next_pos.1 < self.grid.len() as i32
-            && next_pos.0 < self.grid[0].len() as i32
-            && next_pos.0 >= 0
-            && next_pos.1 >= 0
-        {
-            self.grid[next_pos.1 as usize][next_pos.0 as usize].clone()
+        let next_pos = self.guard_pos + self.direction.offset();
+        if self.grid.in_bounds(&next_pos) {
+            self.grid.access_grid(&next_pos).clone()
         } else {
             Location::OutOfBounds
         }
     }
 
     fn step_forward(&mut self) {
-        let next_move = self.move_guard();
-        self.guard_pos = (
-            self.guard_pos.0 + next_move.0,
-            self.guard_pos.1 + next_move.1,
-        );
+        self.guard_pos += self.direction.offset();
     }
 
-    fn get_visited(&self) -> Vec<(i32, i32)> {
-        let mut result: Vec<(i32, i32)> = vec![];
-        for (y, row) in self.grid.iter().enumerate() {
-            for (x, location) in row.iter().enumerate() {
-                match location {
-                    Location::Visited => result.push((x as i32, y as i32)),
-                    _ => continue,
-                }
-            }
-        }
-        result
-    }
-
-    fn current_pos(&self) -> Location {
-        self.grid[self.guard_pos.1 as usize][self.guard_pos.0 as usize].clone()
+    fn get_visited(&self) -> Vec<Point2D> {
+        self.visited.cells().collect()
     }
 
     fn resolve_map(&mut self) {
-        let mut cycle_map: HashMap<(i32, i32), Vec<Direction>> = HashMap::new();
         while !self.out_of_bounds() {
-            // If the direction at a seen position has already occured then we are on the same path and have a cycle
-            let seen_directions: &mut Vec<Direction> =
-                cycle_map.entry(self.guard_pos).or_insert_with(Vec::new);
-
-            if seen_directions.contains(&self.direction) {
+            // If the guard has already been here facing the same way then we are on the same path and have a cycle
+            if !self.visited.set(&self.guard_pos, self.direction.index()) {
                 self.cyclic = true;
                 break;
-            } else {
-                seen_directions.push(self.direction.clone());
             }
 
-            match self.current_pos() {
-                Location::Open => {
-                    self.grid[self.guard_pos.1 as usize][self.guard_pos.0 as usize] =
-                        Location::Visited;
-                }
-                Location::Visited => {}
-                Location::Obstacle | Location::OutOfBounds => {
-                    panic!();
-                }
-            }
-            while true {
-                match self.search_ahead() {
-                    Location::Obstacle => self.rotate_direction(),
-                    Location::Open | Location::Visited | Location::OutOfBounds => break,
-                }
+            while self.search_ahead() == Location::Obstacle {
+                self.rotate_direction();
             }
             self.step_forward();
         }
@@ -212,25 +109,26 @@ impl Map {
         self.cyclic
     }
 
-    fn get_guard_pos(&self) -> (i32, i32) {
+    fn get_guard_pos(&self) -> Point2D {
         self.guard_pos
     }
 }
 
-pub fn solution_one(input: &String) -> i32 {
+pub fn solution_one(input: &str) -> i32 {
     let mut map = Map::new(input);
     map.resolve_map();
     map.get_visited().len() as i32
 }
 
-pub fn solution_two(input: &String) -> i32 {
+pub fn solution_two(input: &str) -> i32 {
     let mut result = 0;
-    let mut map_initial = Map::new(input);
-    let initial_guard_pos = map_initial.get_guard_pos();
+    let map_start = Map::new(input);
+    let initial_guard_pos = map_start.get_guard_pos();
+    let mut map_initial = map_start.clone();
     map_initial.resolve_map();
 
     // Will panic if we start on an obstructed square
-    let visited_squares: Vec<(i32, i32)> = map_initial
+    let visited_squares: Vec<Point2D> = map_initial
         .get_visited()
         .into_iter()
         .filter(|pos| *pos != initial_guard_pos)
@@ -238,8 +136,8 @@ pub fn solution_two(input: &String) -> i32 {
 
     // Only visited squares will hit a new obstacle
     for visited in visited_squares {
-        let mut new_map = Map::new(input);
-        new_map.obstruct(visited);
+        let mut new_map = map_start.clone();
+        new_map.obstruct(&visited);
         new_map.resolve_map();
         if new_map.cyclic() {
             result += 1;
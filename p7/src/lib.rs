@@ -1,3 +1,6 @@
+use helpers::math::strip_digit_suffix;
+use helpers::memo::Memo;
+
 pub fn solution_one(input: &String) -> i128 {
     let mut result = 0;
     for line in input.lines() {
@@ -20,65 +23,37 @@ pub fn solution_two(input: &String) -> i128 {
 }
 
 fn resolve_computation(operands: &Vec<i128>, target: i128) -> bool {
-    if target < 0 {
-        return false;
-    }
-    if operands.len() <= 0 {
-        panic!("Empty operands list");
-    }
-    if operands.len() == 1 {
-        if operands[0] == target {
-            return true;
-        } else {
-            return false;
-        }
-    }
-    let mut op_clone = operands.clone();
-    if let Some(last) = op_clone.pop() {
-        if resolve_computation(&op_clone, target - last) {
-            return true;
-        }
-        if target % last == 0 && resolve_computation(&op_clone, target / last) {
-            return true;
-        }
-    } else {
-        panic!("Vector should not be empty");
-    }
-    false
+    resolve(operands, target, false, &mut Memo::new())
 }
 
 fn resolve_computation_concatenate(operands: &Vec<i128>, target: i128) -> bool {
+    resolve(operands, target, true, &mut Memo::new())
+}
+
+// Works backwards from the last operand. Results are keyed by how many operands
+// are left and the target they still have to reach
+fn resolve(
+    operands: &[i128],
+    target: i128,
+    concatenate: bool,
+    memo: &mut Memo<(usize, i128), bool>,
+) -> bool {
     if target < 0 {
         return false;
     }
-    if operands.len() <= 0 {
+    let Some((last, rest)) = operands.split_last() else {
         panic!("Empty operands list");
-    }
-    if operands.len() == 1 {
-        if operands[0] == target {
-            return true;
-        } else {
-            return false;
-        }
-    }
-    let mut op_clone = operands.clone();
-    if let Some(last) = op_clone.pop() {
-        if resolve_computation_concatenate(&op_clone, target - last) {
-            return true;
-        }
-        if target % last == 0 && resolve_computation_concatenate(&op_clone, target / last) {
-            return true;
-        }
-
-        if let Ok(val) = concatenated_target(&last, &target) {
-            if resolve_computation_concatenate(&op_clone, val) {
-                return true;
-            }
-        }
-    } else {
-        panic!("Vector should not be empty");
-    }
-    false
+    };
+    if rest.is_empty() {
+        return *last == target;
+    }
+    memo.get_or_compute((operands.len(), target), |memo| {
+        resolve(rest, target - last, concatenate, memo)
+            || (target % last == 0 && resolve(rest, target / last, concatenate, memo))
+            || (concatenate
+                && concatenated_target(last, &target)
+                    .is_ok_and(|val| resolve(rest, val, concatenate, memo)))
+    })
 }
 
 fn parse_input(input: &str) -> (i128, Vec<i128>) {
@@ -93,18 +68,10 @@ fn parse_input(input: &str) -> (i128, Vec<i128>) {
 
 // This will apply the opposite of the concatenation to the RHS
 fn concatenated_target<'a>(dividend: &'a i128, target: &'a i128) -> Result<i128, &'a str> {
-    let mut new_target = target - dividend;
-    if new_target % 10 != 0 {
-        return Err("Target not valid");
-    }
-    if new_target < 0 {
+    if target < dividend {
         return Err("Target too small");
     }
-    let digits = dividend.ilog(10) + 1;
-    for _ in 0..digits {
-        new_target /= 10;
-    }
-    Ok(new_target)
+    strip_digit_suffix(*target, *dividend).ok_or("Target not valid")
 }
 
 #[cfg(test)]
@@ -1,45 +1,33 @@
+use helpers::combinatorics::pairs;
+use helpers::hash::FastMap;
 use helpers::{Grid, Point2D};
-use std::collections::HashMap;
 
 struct AntennaGrid {
     grid: Grid<char>,
-    map: HashMap<char, Vec<Point2D>>,
+    map: FastMap<char, Vec<Point2D>>,
 }
 
 impl AntennaGrid {
-    fn new(input: &String) -> Self {
+    fn new(input: &str) -> Self {
         Self {
             grid: Grid::<char>::new(input),
-            map: HashMap::new(),
+            map: FastMap::default(),
         }
     }
 
     fn initialise_antennae(&mut self) {
-        for (i, line) in self.grid.iter().enumerate() {
-            for (j, c) in line.iter().enumerate() {
-                if *c == '.' {
-                    continue;
-                }
-                let new_coords = self.grid.convert_coords_readable(&(i as i32, j as i32));
-                self.map.entry(*c).or_insert_with(Vec::new).push(new_coords);
-            }
-        }
+        self.map = self.grid.group_by_value();
+        self.map.remove(&'.');
     }
 
     fn count_anti_nodes(&self) -> i32 {
         let mut seen_antennae: Vec<Point2D> = vec![];
         for antenna in self.map.keys() {
             let antenna_locations = &self.map[antenna];
-            for i in 0..antenna_locations.len() {
-                for j in 0..antenna_locations.len() {
-                    if i == j {
-                        continue;
-                    }
-                    let anti_node =
-                        self.find_anti_node(&antenna_locations[i], &antenna_locations[j]);
-                    if self.grid.in_bounds(&anti_node) && !seen_antennae.contains(&anti_node) {
-                        seen_antennae.push(anti_node);
-                    }
+            for (first, second) in pairs(antenna_locations) {
+                let anti_node = self.find_anti_node(first, second);
+                if self.grid.in_bounds(&anti_node) && !seen_antennae.contains(&anti_node) {
+                    seen_antennae.push(anti_node);
                 }
             }
         }
@@ -50,27 +38,10 @@ impl AntennaGrid {
         let mut seen_antennae: Vec<Point2D> = vec![];
         for antenna in self.map.keys() {
             let antenna_locations = &self.map[antenna];
-            // Resonant locations on top of the antenae
-            // Can't happen with only one
-            if antenna_locations.len() > 1 {
-                for antenna in antenna_locations {
-                    // Could be seen already
-                    if !seen_antennae.contains(antenna) {
-                        seen_antennae.push(*antenna);
-                    }
-                }
-            }
-            for i in 0..antenna_locations.len() {
-                for j in 0..antenna_locations.len() {
-                    if i == j {
-                        continue;
-                    }
-                    let anti_nodes =
-                        self.find_anti_nodes(&antenna_locations[i], &antenna_locations[j]);
-                    for anti_node in anti_nodes {
-                        if !seen_antennae.contains(&anti_node) {
-                            seen_antennae.push(anti_node);
-                        }
+            for (first, second) in pairs(antenna_locations) {
+                for anti_node in self.find_anti_nodes(first, second) {
+                    if !seen_antennae.contains(&anti_node) {
+                        seen_antennae.push(anti_node);
                     }
                 }
             }
@@ -79,28 +50,27 @@ impl AntennaGrid {
     }
 
     fn find_anti_nodes(&self, first: &Point2D, second: &Point2D) -> Vec<Point2D> {
-        let mut results: Vec<Point2D> = vec![];
-        let anti_node_diff = *second - *first;
-        let mut anti_node = *second + anti_node_diff;
-        while self.grid.in_bounds(&anti_node) {
-            results.push(anti_node.clone());
-            anti_node = anti_node + anti_node_diff;
-        }
-        results
+        // Every lattice point on the line counts, not just whole multiples of the gap,
+        // and both rays start on the antenna itself
+        let step = (*second - *first).primitive();
+        self.grid
+            .ray(second, &step)
+            .chain(self.grid.ray(second, &-step))
+            .collect()
     }
 
     fn find_anti_node(&self, first: &Point2D, second: &Point2D) -> Point2D {
-        *second + *second - *first
+        *second * 2 - *first
     }
 }
 
-pub fn solution_one(input: &String) -> i32 {
-    let mut map = AntennaGrid::new(&input);
+pub fn solution_one(input: &str) -> i32 {
+    let mut map = AntennaGrid::new(input);
     map.initialise_antennae();
     map.count_anti_nodes()
 }
-pub fn solution_two(input: &String) -> i32 {
-    let mut map = AntennaGrid::new(&input);
+pub fn solution_two(input: &str) -> i32 {
+    let mut map = AntennaGrid::new(input);
     map.initialise_antennae();
     map.count_resonant_anti_nodes()
 }
@@ -0,0 +1,102 @@
+use helpers::ranges::RangeSet;
+use std::ops::Range;
+
+struct Disk {
+    // Block span of each file, indexed by file id
+    files: Vec<Range<i64>>,
+    size: i64,
+}
+
+impl Disk {
+    fn new(input: &str) -> Self {
+        let mut files: Vec<Range<i64>> = vec![];
+        let mut position = 0;
+        for (i, c) in input.trim().chars().enumerate() {
+            let length = c.to_digit(10).expect("Disk map should only have digits") as i64;
+            if i % 2 == 0 {
+                files.push(position..position + length);
+            }
+            position += length;
+        }
+        Self {
+            files,
+            size: position,
+        }
+    }
+
+    // Moves single blocks from the end into the leftmost free block
+    fn compact_blocks(&self) -> Vec<Option<usize>> {
+        let mut blocks: Vec<Option<usize>> = vec![None; self.size as usize];
+        for (id, file) in self.files.iter().enumerate() {
+            for block in file.clone() {
+                blocks[block as usize] = Some(id);
+            }
+        }
+        let (mut free, mut last) = (0, blocks.len());
+        loop {
+            while free < blocks.len() && blocks[free].is_some() {
+                free += 1;
+            }
+            while last > 0 && blocks[last - 1].is_none() {
+                last -= 1;
+            }
+            if last == 0 || free >= last - 1 {
+                break;
+            }
+            blocks.swap(free, last - 1);
+        }
+        blocks
+    }
+
+    // Moves whole files, highest id first, into the leftmost gap that fits
+    fn compact_files(&self) -> Vec<Range<i64>> {
+        let mut files = self.files.clone();
+        let mut occupied: RangeSet = files.iter().cloned().collect();
+        for file in files.iter_mut().rev() {
+            let length = file.end - file.start;
+            if let Some(start) = occupied.first_gap(length, &(0..file.start)) {
+                occupied.remove(file.clone());
+                *file = start..start + length;
+                occupied.insert(file.clone());
+            }
+        }
+        files
+    }
+}
+
+pub fn solution_one(input: &str) -> i64 {
+    let disk = Disk::new(input);
+    disk.compact_blocks()
+        .iter()
+        .enumerate()
+        .filter_map(|(position, id)| id.map(|id| (position * id) as i64))
+        .sum()
+}
+
+pub fn solution_two(input: &str) -> i64 {
+    let disk = Disk::new(input);
+    disk.compact_files()
+        .iter()
+        .enumerate()
+        .map(|(id, file)| file.clone().sum::<i64>() * id as i64)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solution_one() {
+        let input = String::from("2333133121414131402");
+        let expected = 1928;
+        assert_eq!(solution_one(&input), expected);
+    }
+
+    #[test]
+    fn test_solution_two() {
+        let input = String::from("2333133121414131402");
+        let expected = 2858;
+        assert_eq!(solution_two(&input), expected);
+    }
+}